@@ -0,0 +1,133 @@
+//! Build script helpers. A procedural macro cannot tell to cargo that a directory
+//! listing changed, so the tests generated by `#[files(...)]` are not expanded again
+//! when you add or remove a file that matches the glob patterns. Call [`track_files()`]
+//! from your `build.rs` (with `rstest` in your `[build-dependencies]`) to fix it:
+//!
+//! ```no_run
+//! // In build.rs main()
+//! rstest::build::track_files(["tests/data"]);
+//! ```
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+/// The environment variable read by the tests generated by `#[files(...)]`.
+pub const FILES_HASH_ENV: &str = "RSTEST_FILES_HASH";
+
+/// Ask cargo to rerun the build script when something change in the given directories
+/// (relative to the crate root) and export an hash of their files list: the tests that
+/// use `#[files(...)]` are compiled again every time the hash changes.
+///
+/// Note that when a build script print a `rerun-if-changed` directive cargo doesn't rerun
+/// it for every change in the package anymore.
+pub fn track_files<P: AsRef<Path>>(dirs: impl IntoIterator<Item = P>) {
+    let dirs = dirs
+        .into_iter()
+        .map(|d| d.as_ref().to_owned())
+        .collect::<Vec<_>>();
+    for dir in &dirs {
+        println!("cargo:rerun-if-changed={}", dir.display());
+    }
+    println!(
+        "cargo:rustc-env={}={:016x}",
+        FILES_HASH_ENV,
+        files_hash(&dirs)
+    );
+}
+
+/// Hash the sorted list of the files paths in `dirs` and their subdirectories: the files
+/// content is not considered because `#[files(...)]` already track every matched file.
+/// Missing directories are just ignored and symbolic links are listed but never followed.
+pub fn files_hash<P: AsRef<Path>>(dirs: &[P]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    for dir in dirs {
+        let dir = dir.as_ref();
+        let mut files = vec![];
+        collect_files(dir, &mut files);
+        files.sort();
+        dir.hash(&mut hasher);
+        for f in files {
+            f.strip_prefix(dir).unwrap_or(&f).hash(&mut hasher);
+        }
+    }
+    hasher.finish()
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    // `DirEntry::file_type()` doesn't follow symlinks: a link to a directory is
+    // listed like a file, so a link loop cannot recurse forever
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        if entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+            collect_files(&path, files);
+        } else {
+            files.push(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use temp_testdir::TempDir;
+
+    fn write(dir: &Path, name: &str, content: &str) {
+        let path = dir.join(name);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn files_hash_should_change_when_a_file_is_added_or_removed() {
+        let root = TempDir::default();
+        write(&root, "data/a.txt", "a");
+        let original = files_hash(&[root.join("data")]);
+
+        write(&root, "data/sub/b.txt", "b");
+        let added = files_hash(&[root.join("data")]);
+        std::fs::remove_file(root.join("data/sub/b.txt")).unwrap();
+
+        assert_ne!(original, added);
+        assert_eq!(original, files_hash(&[root.join("data")]));
+    }
+
+    #[test]
+    fn files_hash_should_not_depend_on_files_content() {
+        let root = TempDir::default();
+        write(&root, "data/a.txt", "a");
+        let original = files_hash(&[root.join("data")]);
+
+        write(&root, "data/a.txt", "changed");
+
+        assert_eq!(original, files_hash(&[root.join("data")]));
+    }
+
+    #[test]
+    fn files_hash_should_consider_missing_directories_as_empty() {
+        let root = TempDir::default();
+        let dir = root.join("data");
+        let missing = files_hash(&[&dir]);
+
+        std::fs::create_dir(&dir).unwrap();
+
+        assert_eq!(missing, files_hash(&[&dir]));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn files_hash_should_not_follow_directory_symlinks() {
+        let root = TempDir::default();
+        write(&root, "data/a.txt", "a");
+        let original = files_hash(&[root.join("data")]);
+
+        std::os::unix::fs::symlink(root.join("data"), root.join("data/loop")).unwrap();
+
+        assert_ne!(original, files_hash(&[root.join("data")]));
+    }
+}
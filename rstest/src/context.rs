@@ -0,0 +1,145 @@
+//! The test that is running in the current thread: every test rendered by `rstest`
+//! enters its context before resolve its fixtures, so fixtures can know which test
+//! they are built for.
+use std::{
+    cell::RefCell,
+    fmt::Debug,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+type Args = Vec<(&'static str, String)>;
+
+#[derive(Clone)]
+struct Test {
+    name: &'static str,
+    /// Shared with the threads that inherit the context
+    args: Arc<Mutex<Args>>,
+}
+
+impl Test {
+    fn args(&self) -> MutexGuard<'_, Args> {
+        self.args.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Vec<Test>> = const { RefCell::new(Vec::new()) };
+}
+
+/// The full path of the `rstest` test running in the current thread, if any.
+pub fn current_test() -> Option<&'static str> {
+    CURRENT.with(|current| current.borrow().last().map(|test| test.name))
+}
+
+/// The arguments traced by the test running in the current thread.
+pub(crate) fn traced_args() -> Vec<(&'static str, String)> {
+    CURRENT.with(|current| {
+        current
+            .borrow()
+            .last()
+            .map(|test| test.args().clone())
+            .unwrap_or_default()
+    })
+}
+
+/// The context of the test running in the current thread, that another thread can
+/// enter to run the test's code: the arguments traced by both threads are shared.
+pub(crate) struct Inherited(Test);
+
+pub(crate) fn inherit() -> Option<Inherited> {
+    CURRENT.with(|current| current.borrow().last().cloned().map(Inherited))
+}
+
+impl Inherited {
+    pub(crate) fn enter(self) -> TestContextGuard {
+        CURRENT.with(|current| current.borrow_mut().push(self.0));
+        TestContextGuard(())
+    }
+}
+
+#[doc(hidden)]
+pub struct TestContext(());
+
+impl TestContext {
+    /// Enter in the test's context: leave it when the returned guard is dropped.
+    pub fn enter(name: &'static str) -> TestContextGuard {
+        CURRENT.with(|current| {
+            current.borrow_mut().push(Test {
+                name,
+                args: Default::default(),
+            })
+        });
+        TestContextGuard(())
+    }
+
+    /// Record the value of a traced argument of the current test.
+    pub fn trace(name: &'static str, value: &dyn Debug) {
+        CURRENT.with(|current| {
+            if let Some(test) = current.borrow().last() {
+                let value = format!("{:?}", value);
+                let mut args = test.args();
+                match args.iter_mut().find(|(arg, _)| *arg == name) {
+                    Some((_, old)) => *old = value,
+                    None => args.push((name, value)),
+                }
+            }
+        })
+    }
+}
+
+#[doc(hidden)]
+pub struct TestContextGuard(());
+
+impl Drop for TestContextGuard {
+    fn drop(&mut self) {
+        let _ = CURRENT.try_with(|current| current.borrow_mut().pop());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn should_track_the_innermost_test() {
+        assert_eq!(None, current_test());
+        {
+            let _outer = TestContext::enter("outer");
+            {
+                let _inner = TestContext::enter("inner");
+                assert_eq!(Some("inner"), current_test());
+            }
+            assert_eq!(Some("outer"), current_test());
+        }
+        assert_eq!(None, current_test());
+    }
+
+    #[test]
+    fn should_record_the_last_traced_values() {
+        let _test = TestContext::enter("test");
+        TestContext::trace("a", &42);
+        TestContext::trace("b", &"foo");
+        TestContext::trace("a", &43);
+
+        assert_eq!(
+            vec![("a", "43".to_string()), ("b", "\"foo\"".to_string())],
+            traced_args()
+        );
+    }
+
+    #[test]
+    fn should_share_the_traced_values_with_the_threads_that_inherit_the_context() {
+        let _test = TestContext::enter("test");
+        let inherited = inherit().unwrap();
+
+        std::thread::spawn(move || {
+            let _test = inherited.enter();
+            assert_eq!(Some("test"), current_test());
+            TestContext::trace("a", &42);
+        })
+        .join()
+        .unwrap();
+
+        assert_eq!(vec![("a", "42".to_string())], traced_args());
+    }
+}
@@ -234,10 +234,21 @@
 //!
 //! - **`async-timeout`** *(enabled by default)* — Implement timeout for async
 //! tests.
+//! - **`tokio-timeout`** — Use the tokio timer for async tests in a tokio runtime.
 
+pub mod build;
+pub mod context;
 #[doc(hidden)]
 pub mod magic_conversion;
 #[doc(hidden)]
+pub mod once;
+pub mod retry;
+pub mod rng;
+pub mod scope;
+pub mod strategy;
+pub mod teardown;
 pub mod timeout;
 
+pub use once::Reset;
+pub use rng::{test_rng, TestRng};
 pub use rstest_macros::{fixture, rstest};
@@ -0,0 +1,688 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    future::{poll_fn, Future},
+    panic::{catch_unwind, AssertUnwindSafe},
+    sync::{Arc, Condvar, Mutex, MutexGuard, Once, OnceLock, RwLock},
+    task::{Poll, Waker},
+    thread::ThreadId,
+};
+
+use crate::scope::Shared;
+
+/// The once cell used to implement sync `#[once]` fixtures. The value is shared by
+/// all tests that can run in different threads, so the static cell requires that `T` is
+/// `Send + Sync`: the compiler reject non `Sync` fixtures instead of share them.
+pub struct OnceCell<T>(OnceLock<T>);
+
+impl<T> OnceCell<T> {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Self(OnceLock::new())
+    }
+
+    pub fn get_or_init<F: FnOnce() -> T>(&self, init: F) -> &T {
+        self.0.get_or_init(init)
+    }
+}
+
+impl<T> Default for OnceCell<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Default)]
+struct InitState {
+    initializing: bool,
+    waiters: Vec<Waker>,
+}
+
+/// A runtime agnostic once cell used to implement async `#[once]` fixtures: the first
+/// task that await `get_or_init()` compute the value and all the others wait until the value
+/// is ready. If the initialization future is dropped or panics the next waiting task
+/// take its place.
+pub struct AsyncOnceCell<T> {
+    value: OnceLock<T>,
+    state: Mutex<Option<InitState>>,
+}
+
+impl<T> AsyncOnceCell<T> {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Self {
+            value: OnceLock::new(),
+            state: Mutex::new(None),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, Option<InitState>> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn try_start_init(&self) -> Option<InitGuard<'_, T>> {
+        let mut state = self.state();
+        let state = state.get_or_insert_with(Default::default);
+        if state.initializing {
+            None
+        } else {
+            state.initializing = true;
+            Some(InitGuard(self))
+        }
+    }
+
+    async fn wait_init(&self) {
+        poll_fn(|cx| {
+            let mut state = self.state();
+            match state.as_mut() {
+                Some(state) if state.initializing && self.value.get().is_none() => {
+                    // A task can be polled many times while it waits: register it just once
+                    if !state.waiters.iter().any(|w| w.will_wake(cx.waker())) {
+                        state.waiters.push(cx.waker().clone());
+                    }
+                    Poll::Pending
+                }
+                _ => Poll::Ready(()),
+            }
+        })
+        .await
+    }
+
+    pub async fn get_or_init<F: Future<Output = T>>(&self, init: F) -> &T {
+        loop {
+            if let Some(value) = self.value.get() {
+                return value;
+            }
+            if let Some(_guard) = self.try_start_init() {
+                let _ = self.value.set(init.await);
+                return self.value.get().unwrap();
+            }
+            self.wait_init().await;
+        }
+    }
+}
+
+impl<T> Default for AsyncOnceCell<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The instance of a generic `#[once]` fixture: the types of its generic parameters and
+/// `impl` arguments and the values of its const parameters.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct TypeKey {
+    types: Vec<TypeId>,
+    consts: Vec<u128>,
+}
+
+impl TypeKey {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with<T: ?Sized + 'static>(mut self) -> Self {
+        self.types.push(TypeId::of::<T>());
+        self
+    }
+
+    pub fn with_type_of<T: ?Sized + 'static>(self, _value: &T) -> Self {
+        self.with::<T>()
+    }
+
+    pub fn with_const(mut self, value: u128) -> Self {
+        self.consts.push(value);
+        self
+    }
+}
+
+type Cells = HashMap<(TypeId, TypeKey), &'static (dyn Any + Send + Sync)>;
+
+/// Once cells indexed by fixture instance: used to implement generic `#[once]` fixtures
+/// where we need a different value for each concrete instance. Cells are never released.
+pub struct OnceTypeMap(Mutex<Option<Cells>>);
+
+impl OnceTypeMap {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Self(Mutex::new(None))
+    }
+
+    fn cell<C: Default + Send + Sync + 'static>(&self, key: TypeKey) -> &'static C {
+        let cell = *self
+            .0
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get_or_insert_with(Default::default)
+            .entry((TypeId::of::<C>(), key))
+            .or_insert_with(|| Box::leak(Box::<C>::default()));
+        cell.downcast_ref().unwrap()
+    }
+
+    pub fn get_or_init<T: Send + Sync + 'static, F: FnOnce() -> T>(
+        &self,
+        key: TypeKey,
+        init: F,
+    ) -> &'static T {
+        self.cell::<OnceLock<T>>(key).get_or_init(init)
+    }
+
+    pub async fn get_or_init_async<T: Send + Sync + 'static, F: Future<Output = T>>(
+        &self,
+        key: TypeKey,
+        init: F,
+    ) -> &'static T {
+        self.cell::<AsyncOnceCell<T>>(key).get_or_init(init).await
+    }
+}
+
+type Dropper = Box<dyn FnOnce() + Send>;
+
+static AT_EXIT: Mutex<Vec<Dropper>> = Mutex::new(Vec::new());
+
+extern "C" fn drop_all_at_exit() {
+    let droppers = std::mem::take(&mut *AT_EXIT.lock().unwrap_or_else(|e| e.into_inner()));
+    for dropper in droppers.into_iter().rev() {
+        if catch_unwind(AssertUnwindSafe(dropper)).is_err() {
+            eprintln!("rstest: a #[once(drop)] fixture panicked while dropped at exit");
+        }
+    }
+}
+
+#[cfg(any(unix, windows))]
+fn register_drop_all_at_exit() {
+    unsafe {
+        libc::atexit(drop_all_at_exit);
+    }
+}
+
+// No portable way to run code at exit here: values are just leaked
+#[cfg(not(any(unix, windows)))]
+fn register_drop_all_at_exit() {}
+
+/// The value of a `#[once(drop)]` fixture: every test get a [`Shared`] copy of it and the
+/// value is dropped at exit when no other copy is alive. A copy that is still alive at exit
+/// (e.g. in the thread of a timed out test) keeps the value and it'll be dropped with
+/// the last copy or never.
+pub struct DropAtExit<T>(Arc<Mutex<Option<Shared<T>>>>);
+
+impl<T> DropAtExit<T> {
+    /// Return a copy of the value.
+    ///
+    /// # Panics
+    ///
+    /// If it's called after the value is released at exit.
+    pub fn get(&self) -> Shared<T> {
+        Self::lock(&self.0)
+            .as_ref()
+            .cloned()
+            .expect("rstest: #[once(drop)] fixture used after the process started to exit")
+    }
+
+    fn lock(value: &Mutex<Option<Shared<T>>>) -> MutexGuard<'_, Option<Shared<T>>> {
+        value.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn release(value: &Mutex<Option<Shared<T>>>) {
+        let value = Self::lock(value).take();
+        drop(value)
+    }
+}
+
+/// Wrap `value` in a [`DropAtExit`] that release it when the process exits (values are
+/// released in reverse order): used to implement `#[once(drop)]` fixtures. On platforms
+/// other than unix and windows the value is never dropped.
+pub fn drop_at_exit<T: Send + Sync + 'static>(value: T) -> DropAtExit<T> {
+    let value = Arc::new(Mutex::new(Some(Shared::new(value))));
+    let at_exit_value = value.clone();
+    at_exit(move || DropAtExit::release(&at_exit_value));
+    DropAtExit(value)
+}
+
+/// Register `dropper` to be called when the process exits (in reverse registration order).
+pub(crate) fn at_exit(dropper: impl FnOnce() + Send + 'static) {
+    static REGISTER: Once = Once::new();
+    REGISTER.call_once(register_drop_all_at_exit);
+    AT_EXIT
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .push(Box::new(dropper));
+}
+
+/// The contract of `#[once(reset)]` fixtures: `reset()` is called every time a test
+/// receive the fixture value and should restore the value to a clean state.
+pub trait Reset {
+    fn reset(&self);
+}
+
+impl<T: Default> Reset for Mutex<T> {
+    fn reset(&self) {
+        *self.lock().unwrap_or_else(|e| e.into_inner()) = T::default();
+    }
+}
+
+impl<T: Default> Reset for RwLock<T> {
+    fn reset(&self) {
+        *self.write().unwrap_or_else(|e| e.into_inner()) = T::default();
+    }
+}
+
+/// Tests that use a `#[once(reset)]` fixture should not run concurrently, otherwise a test
+/// could reset the value while another one is using it. The test acquire the access when
+/// resolve the fixture and hold the returned guard till its end: the same thread can
+/// acquire it more than once.
+pub struct ExclusiveAccess {
+    state: Mutex<AccessState>,
+    released: Condvar,
+}
+
+struct AccessState {
+    owner: Option<(ThreadId, usize)>,
+    waiters: Vec<Waker>,
+}
+
+impl AccessState {
+    fn try_acquire(&mut self) -> bool {
+        let me = std::thread::current().id();
+        match self.owner.as_mut() {
+            Some((id, count)) if *id == me => *count += 1,
+            Some(_) => return false,
+            None => self.owner = Some((me, 1)),
+        }
+        true
+    }
+}
+
+impl ExclusiveAccess {
+    #[allow(clippy::new_without_default)]
+    pub const fn new() -> Self {
+        Self {
+            state: Mutex::new(AccessState {
+                owner: None,
+                waiters: Vec::new(),
+            }),
+            released: Condvar::new(),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, AccessState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Wait until no other test is using the fixture: the access is held till the returned
+    /// guard is dropped.
+    pub fn acquire(&'static self) -> ExclusiveAccessGuard {
+        let mut state = self.state();
+        while !state.try_acquire() {
+            state = self.released.wait(state).unwrap_or_else(|e| e.into_inner());
+        }
+        ExclusiveAccessGuard(self)
+    }
+
+    /// Like [`ExclusiveAccess::acquire()`] but doesn't block the executor's thread while
+    /// waiting: used by `async` fixtures.
+    pub async fn acquire_async(&'static self) -> ExclusiveAccessGuard {
+        std::future::poll_fn(|cx| {
+            let mut state = self.state();
+            if state.try_acquire() {
+                return Poll::Ready(ExclusiveAccessGuard(self));
+            }
+            if !state.waiters.iter().any(|w| w.will_wake(cx.waker())) {
+                state.waiters.push(cx.waker().clone());
+            }
+            Poll::Pending
+        })
+        .await
+    }
+
+    fn release(&self) {
+        let waiters = {
+            let mut state = self.state();
+            match state.owner.as_mut() {
+                Some((_, count)) if *count > 1 => {
+                    *count -= 1;
+                    return;
+                }
+                _ => state.owner = None,
+            }
+            std::mem::take(&mut state.waiters)
+        };
+        self.released.notify_all();
+        waiters.into_iter().for_each(Waker::wake);
+    }
+}
+
+/// The access shared by all `#[once(reset)]` fixtures: a lock for each fixture would
+/// deadlock two tests that take the same fixtures in a different order.
+static RESET_ACCESS: ExclusiveAccess = ExclusiveAccess::new();
+
+/// Acquire the access to the `#[once(reset)]` fixtures: see [`ExclusiveAccess::acquire()`].
+pub fn acquire_reset_access() -> ExclusiveAccessGuard {
+    RESET_ACCESS.acquire()
+}
+
+/// Acquire the access to the `#[once(reset)]` fixtures without block the executor's
+/// thread: see [`ExclusiveAccess::acquire_async()`].
+pub async fn acquire_reset_access_async() -> ExclusiveAccessGuard {
+    RESET_ACCESS.acquire_async().await
+}
+
+/// Hold the access to a `#[once(reset)]` fixture and release it when dropped.
+#[must_use]
+pub struct ExclusiveAccessGuard(&'static ExclusiveAccess);
+
+impl Drop for ExclusiveAccessGuard {
+    fn drop(&mut self) {
+        self.0.release()
+    }
+}
+
+/// Release the initialization lock and wake up all waiting tasks: also
+/// when the initialization future is dropped before completion
+struct InitGuard<'a, T>(&'a AsyncOnceCell<T>);
+
+impl<T> Drop for InitGuard<'_, T> {
+    fn drop(&mut self) {
+        let waiters = self
+            .0
+            .state()
+            .as_mut()
+            .map(|state| {
+                state.initializing = false;
+                std::mem::take(&mut state.waiters)
+            })
+            .unwrap_or_default();
+        waiters.into_iter().for_each(Waker::wake);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    #[test]
+    fn once_cell_should_init_value_just_once_across_threads() {
+        static CELL: OnceCell<u32> = OnceCell::new();
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+        let handles = (0..10)
+            .map(|i| {
+                std::thread::spawn(move || {
+                    CELL.get_or_init(|| {
+                        CALLS.fetch_add(1, Ordering::SeqCst);
+                        i
+                    })
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let values = handles
+            .into_iter()
+            .map(|h| *h.join().unwrap())
+            .collect::<Vec<_>>();
+
+        assert!(values.iter().all(|&v| v == values[0]));
+        assert_eq!(1, CALLS.load(Ordering::SeqCst));
+    }
+
+    #[async_std::test]
+    async fn async_once_cell_should_init_value_just_once() {
+        static CELL: AsyncOnceCell<u32> = AsyncOnceCell::new();
+        static CALLS: AtomicUsize = AtomicUsize::new(0);
+
+        let init = || async {
+            CALLS.fetch_add(1, Ordering::SeqCst);
+            async_std::task::sleep(Duration::from_millis(10)).await;
+            42
+        };
+
+        let tasks = (0..10)
+            .map(|_| async_std::task::spawn(async move { CELL.get_or_init(init()).await }))
+            .collect::<Vec<_>>();
+
+        for t in tasks {
+            assert_eq!(&42, t.await);
+        }
+        assert_eq!(1, CALLS.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn async_once_cell_should_work_across_different_runtimes() {
+        static CELL: AsyncOnceCell<u32> = AsyncOnceCell::new();
+
+        let handles = (0..4)
+            .map(|i| {
+                std::thread::spawn(move || {
+                    tokio::runtime::Builder::new_current_thread()
+                        .enable_all()
+                        .build()
+                        .unwrap()
+                        .block_on(CELL.get_or_init(async move {
+                            tokio::task::yield_now().await;
+                            i
+                        }))
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let values = handles
+            .into_iter()
+            .map(|h| *h.join().unwrap())
+            .collect::<Vec<_>>();
+
+        assert!(values.iter().all(|&v| v == values[0]));
+    }
+
+    #[async_std::test]
+    async fn async_once_cell_should_init_again_if_first_initialization_is_dropped() {
+        static CELL: AsyncOnceCell<u32> = AsyncOnceCell::new();
+
+        let dropped = async_std::future::timeout(
+            Duration::from_millis(10),
+            CELL.get_or_init(async_std::future::pending()),
+        )
+        .await;
+
+        assert!(dropped.is_err());
+        assert_eq!(&42, CELL.get_or_init(async { 42 }).await);
+    }
+
+    #[test]
+    fn async_once_cell_should_register_a_waiting_task_just_once() {
+        struct Noop;
+        impl std::task::Wake for Noop {
+            fn wake(self: Arc<Self>) {}
+        }
+        let waker = Waker::from(Arc::new(Noop));
+        let mut cx = std::task::Context::from_waker(&waker);
+        let cell = AsyncOnceCell::<u32>::new();
+        let _init = cell.try_start_init().unwrap();
+        let mut waiting = Box::pin(cell.wait_init());
+
+        for _ in 0..10 {
+            assert!(waiting.as_mut().poll(&mut cx).is_pending());
+        }
+
+        assert_eq!(1, cell.state().as_ref().unwrap().waiters.len());
+    }
+
+    #[test]
+    fn once_type_map_should_init_a_value_for_each_type() {
+        static CELLS: OnceTypeMap = OnceTypeMap::new();
+
+        fn get<T: Default + Send + Sync + 'static>() -> &'static T {
+            CELLS.get_or_init(TypeKey::new().with::<T>(), T::default)
+        }
+
+        assert_eq!(&0, get::<u32>());
+        assert_eq!("", get::<String>());
+        assert!(std::ptr::eq(get::<u32>(), get::<u32>()));
+        assert!(std::ptr::eq(get::<String>(), get::<String>()));
+        assert_eq!(&42, CELLS.get_or_init(TypeKey::new(), || 42_u64));
+        assert_eq!(&42, CELLS.get_or_init(TypeKey::new(), || 0_u64));
+    }
+
+    #[test]
+    fn once_type_map_should_init_a_value_for_each_key() {
+        static CELLS: OnceTypeMap = OnceTypeMap::new();
+
+        fn keyed<T: std::fmt::Debug + 'static>(v: T) -> &'static String {
+            CELLS.get_or_init(TypeKey::new().with::<T>(), || format!("{:?}", v))
+        }
+
+        assert_eq!("1", keyed(1_u32));
+        assert_eq!("false", keyed(false));
+        assert_eq!("1", keyed(2_u32));
+        assert_eq!(
+            "3",
+            CELLS.get_or_init(TypeKey::new().with_const(3), || "3".to_string())
+        );
+        assert_eq!(
+            "4",
+            CELLS.get_or_init(TypeKey::new().with_const(4), || "4".to_string())
+        );
+    }
+
+    #[test]
+    fn drop_at_exit_should_not_drop_values_before_exit() {
+        struct D(Arc<AtomicUsize>);
+        impl Drop for D {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+        let dropped = Arc::new(AtomicUsize::new(0));
+
+        let value = drop_at_exit(D(dropped.clone()));
+
+        assert_eq!(0, value.get().0.load(Ordering::SeqCst));
+        assert_eq!(0, dropped.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn drop_at_exit_should_keep_the_value_while_a_copy_is_alive() {
+        struct D(Arc<AtomicUsize>);
+        impl Drop for D {
+            fn drop(&mut self) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+        let dropped = Arc::new(AtomicUsize::new(0));
+        let value = drop_at_exit(D(dropped.clone()));
+        let copy = value.get();
+
+        DropAtExit::release(&value.0);
+
+        assert_eq!(0, dropped.load(Ordering::SeqCst));
+        drop(copy);
+        assert_eq!(1, dropped.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    #[should_panic(expected = "after the process started to exit")]
+    fn drop_at_exit_should_panic_if_used_after_release() {
+        let value = drop_at_exit(42);
+
+        DropAtExit::release(&value.0);
+
+        value.get();
+    }
+
+    #[test]
+    fn reset_should_restore_default_value() {
+        let m = Mutex::new(vec![1, 2]);
+        let rw = RwLock::new(String::from("dirty"));
+
+        m.reset();
+        rw.reset();
+
+        assert!(m.lock().unwrap().is_empty());
+        assert!(rw.read().unwrap().is_empty());
+    }
+
+    #[test]
+    fn exclusive_access_should_serialize_tests_and_be_reentrant() {
+        static ACCESS: ExclusiveAccess = ExclusiveAccess::new();
+        static RUNNING: AtomicUsize = AtomicUsize::new(0);
+
+        let handles = (0..4)
+            .map(|_| {
+                std::thread::spawn(|| {
+                    let _first = ACCESS.acquire();
+                    let _second = ACCESS.acquire();
+                    assert_eq!(0, RUNNING.fetch_add(1, Ordering::SeqCst));
+                    std::thread::sleep(Duration::from_millis(10));
+                    RUNNING.fetch_sub(1, Ordering::SeqCst);
+                })
+            })
+            .collect::<Vec<_>>();
+
+        for h in handles {
+            h.join().unwrap();
+        }
+        assert!(ACCESS.state().owner.is_none());
+    }
+
+    #[test]
+    fn exclusive_access_should_serialize_async_tests_without_block_them() {
+        static ACCESS: ExclusiveAccess = ExclusiveAccess::new();
+        static RUNNING: AtomicUsize = AtomicUsize::new(0);
+
+        let sync_access = ACCESS.acquire();
+        let handles = (0..4)
+            .map(|_| {
+                std::thread::spawn(|| {
+                    async_std::task::block_on(async {
+                        let _first = ACCESS.acquire_async().await;
+                        let _second = ACCESS.acquire_async().await;
+                        assert_eq!(0, RUNNING.fetch_add(1, Ordering::SeqCst));
+                        async_std::task::sleep(Duration::from_millis(10)).await;
+                        RUNNING.fetch_sub(1, Ordering::SeqCst);
+                    })
+                })
+            })
+            .collect::<Vec<_>>();
+        // Every task should wait for the access without block its thread
+        while ACCESS.state().waiters.len() < 4 {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        drop(sync_access);
+
+        for h in handles {
+            h.join().unwrap();
+        }
+        assert!(ACCESS.state().owner.is_none());
+    }
+
+    #[async_std::test]
+    async fn once_type_map_should_init_async_values_for_each_type() {
+        static CELLS: OnceTypeMap = OnceTypeMap::new();
+
+        assert_eq!(
+            &42,
+            CELLS
+                .get_or_init_async(TypeKey::new(), async { 42_u32 })
+                .await
+        );
+        assert_eq!(
+            &42,
+            CELLS
+                .get_or_init_async(TypeKey::new(), async { 0_u32 })
+                .await
+        );
+        assert_eq!(
+            &"42",
+            CELLS
+                .get_or_init_async(TypeKey::new(), async { "42" })
+                .await
+        );
+    }
+}
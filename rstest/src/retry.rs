@@ -0,0 +1,120 @@
+//! Retry flaky tests: a `#[retries(n)]` test that fails (panics or returns an `Err`)
+//! is executed again, resolving all its fixtures from scratch, up to `n` more times.
+//! The test fails only if every attempt fails and it reports the last failure.
+use std::{
+    future::Future,
+    panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
+    task::Poll,
+};
+
+use crate::strategy::Outcome;
+
+fn attempt_failed(attempt: u32, retries: u32) {
+    println!(
+        "{:-^40}",
+        format!(" ATTEMPT {}/{} FAILED ", attempt, retries + 1)
+    );
+}
+
+#[doc(hidden)]
+pub fn execute_with_retries<R: Outcome>(retries: u32, test: impl Fn() -> R) -> R {
+    let mut attempt = 1;
+    loop {
+        let last = attempt > retries;
+        match catch_unwind(AssertUnwindSafe(&test)) {
+            Ok(outcome) if last || !outcome.is_failure() => return outcome,
+            Err(payload) if last => resume_unwind(payload),
+            _ => attempt_failed(attempt, retries),
+        }
+        attempt += 1;
+    }
+}
+
+#[doc(hidden)]
+pub async fn execute_with_retries_async<R: Outcome, Fut: Future<Output = R>>(
+    retries: u32,
+    test: impl Fn() -> Fut,
+) -> R {
+    let mut attempt = 1;
+    loop {
+        let last = attempt > retries;
+        let mut future = Box::pin(test());
+        let result = std::future::poll_fn(|cx| {
+            match catch_unwind(AssertUnwindSafe(|| future.as_mut().poll(cx))) {
+                Ok(Poll::Ready(outcome)) => Poll::Ready(Ok(outcome)),
+                Ok(Poll::Pending) => Poll::Pending,
+                Err(payload) => Poll::Ready(Err(payload)),
+            }
+        })
+        .await;
+        match result {
+            Ok(outcome) if last || !outcome.is_failure() => return outcome,
+            Err(payload) if last => resume_unwind(payload),
+            _ => attempt_failed(attempt, retries),
+        }
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    fn flaky(attempts: &Cell<u32>, fails: u32) {
+        attempts.set(attempts.get() + 1);
+        assert!(attempts.get() > fails, "attempt {} failed", attempts.get());
+    }
+
+    #[test]
+    fn should_pass_at_the_first_success() {
+        for (retries, fails) in [(0, 0), (3, 0), (3, 2), (3, 3)] {
+            let attempts = Cell::new(0);
+
+            execute_with_retries(retries, || flaky(&attempts, fails));
+
+            assert_eq!(fails + 1, attempts.get(), "retries = {}", retries);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "attempt 3 failed")]
+    fn should_report_the_last_panic() {
+        let attempts = Cell::new(0);
+
+        execute_with_retries(2, || flaky(&attempts, 5));
+    }
+
+    #[test]
+    fn should_retry_the_failed_results() {
+        let attempts = Cell::new(0);
+
+        let result = execute_with_retries(2, || {
+            attempts.set(attempts.get() + 1);
+            Err::<(), _>(attempts.get())
+        });
+
+        assert_eq!(Err(3), result);
+    }
+
+    #[async_std::test]
+    async fn should_retry_the_whole_future() {
+        let attempts = Cell::new(0);
+
+        execute_with_retries_async(3, || async {
+            async_std::task::yield_now().await;
+            flaky(&attempts, 2)
+        })
+        .await;
+
+        assert_eq!(3, attempts.get());
+    }
+
+    #[async_std::test]
+    #[should_panic(expected = "attempt 2 failed")]
+    async fn should_report_the_last_async_panic() {
+        let attempts = Cell::new(0);
+
+        execute_with_retries_async(1, || async { flaky(&attempts, 5) }).await;
+    }
+}
@@ -0,0 +1,153 @@
+//! A random numbers generator fixture: `test_rng` is seeded by `RSTEST_SEED`
+//! environment variable if it's defined, otherwise by the test name. So the values
+//! are the same at every run and a failure can be reproduced from its seed.
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+};
+
+use crate::{
+    context::current_test,
+    strategy::{Rng, Strategy, SEED_ENV},
+};
+
+/// A seeded random numbers generator: use [`TestRng::gen()`] to generate a value
+/// by a [`Strategy`] or the [`Rng`] methods directly.
+///
+/// The `Debug` representation shows the seed, so a failing test that traces its
+/// arguments shows how to reproduce it.
+#[derive(Clone)]
+pub struct TestRng {
+    seed: u64,
+    rng: Rng,
+}
+
+impl TestRng {
+    pub fn new(seed: u64) -> Self {
+        Self {
+            seed,
+            rng: Rng::new(seed),
+        }
+    }
+
+    /// Seeded by `RSTEST_SEED` if defined or by the hash of `name`.
+    pub fn for_test(name: &str) -> Self {
+        Self::new(seed_from_env().unwrap_or_else(|| fnv1a(name)))
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn gen<S: Strategy>(&mut self, strategy: S) -> S::Value {
+        strategy.generate(&mut self.rng)
+    }
+}
+
+impl fmt::Debug for TestRng {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "TestRng {{ seed: {} }} ({}={})",
+            self.seed, SEED_ENV, self.seed
+        )
+    }
+}
+
+impl Deref for TestRng {
+    type Target = Rng;
+
+    fn deref(&self) -> &Rng {
+        &self.rng
+    }
+}
+
+impl DerefMut for TestRng {
+    fn deref_mut(&mut self) -> &mut Rng {
+        &mut self.rng
+    }
+}
+
+fn seed_from_env() -> Option<u64> {
+    let value = std::env::var(SEED_ENV).ok()?;
+    match value.trim().parse() {
+        Ok(seed) => Some(seed),
+        Err(_) => panic!("Invalid {} value '{}'", SEED_ENV, value),
+    }
+}
+
+/// A hash that doesn't change between runs, rust versions or platforms.
+fn fnv1a(name: &str) -> u64 {
+    name.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// The `test_rng` fixture: a [`TestRng`] seeded for the current test. Use it like any
+/// other fixture:
+///
+/// ```
+/// use rstest::*;
+///
+/// #[rstest]
+/// fn shuffle_keeps_all_items(mut test_rng: TestRng) {
+///     let mut items = (0..10).collect::<Vec<u32>>();
+///     for i in (1..items.len()).rev() {
+///         let j = test_rng.below(i as u64 + 1) as usize;
+///         items.swap(i, j);
+///     }
+///     items.sort();
+///     assert_eq!((0..10).collect::<Vec<u32>>(), items);
+/// }
+/// ```
+pub fn test_rng() -> TestRng {
+    TestRng::for_test(current_test().unwrap_or_default())
+}
+
+#[doc(hidden)]
+#[allow(non_camel_case_types)]
+pub struct test_rng {}
+
+impl test_rng {
+    pub fn get() -> TestRng {
+        test_rng()
+    }
+
+    #[allow(clippy::should_implement_trait)]
+    pub fn default() -> TestRng {
+        Self::get()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::context::TestContext;
+
+    #[test]
+    fn should_be_seeded_by_test_name() {
+        let values = |name| {
+            let _context = TestContext::enter(name);
+            let mut rng = test_rng();
+            (0..5).map(|_| rng.next_u64()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(values("a::test"), values("a::test"));
+        assert_ne!(values("a::test"), values("b::test"));
+    }
+
+    #[test]
+    fn debug_should_show_the_seed() {
+        assert_eq!(
+            "TestRng { seed: 42 } (RSTEST_SEED=42)",
+            format!("{:?}", TestRng::new(42))
+        );
+    }
+
+    #[test]
+    fn gen_should_use_strategies() {
+        let mut rng = TestRng::new(42);
+
+        assert!((10..20).contains(&rng.gen(10..20)));
+    }
+}
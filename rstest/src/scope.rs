@@ -0,0 +1,257 @@
+//! Fixtures scoped to the tests generated by a single `#[rstest]` function: a
+//! `#[scope(module)]` fixture is computed once for all `case_N` and matrix tests of the
+//! same function and dropped when the last of them is terminated or, if some of them don't
+//! run (e.g. filtered out), when the test process exits.
+use std::{
+    any::{Any, TypeId},
+    cell::RefCell,
+    collections::HashMap,
+    future::Future,
+    ops::Deref,
+    sync::{Arc, Mutex, MutexGuard, Once, OnceLock},
+};
+
+use crate::once::AsyncOnceCell;
+
+/// The value returned by a `#[scope(module)]` fixture: a shared reference to the
+/// fixture's value that all tests of the same `#[rstest]` function receive.
+#[derive(Debug)]
+pub struct Shared<T>(Arc<T>);
+
+impl<T> Shared<T> {
+    pub fn new(value: T) -> Self {
+        Self(Arc::new(value))
+    }
+}
+
+impl<T> Clone for Shared<T> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> Deref for Shared<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> AsRef<T> for Shared<T> {
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
+type Cells = HashMap<TypeId, Arc<dyn Any + Send + Sync>>;
+
+#[derive(Default)]
+struct GroupState {
+    remaining: usize,
+    cells: Cells,
+}
+
+/// All tests rendered from the same `#[rstest]` function belong to a group that knows how
+/// many tests it contains: scoped values are released when all tests left the group.
+#[doc(hidden)]
+pub struct TestGroup {
+    size: usize,
+    state: Mutex<Option<GroupState>>,
+    release_at_exit: Once,
+}
+
+thread_local! {
+    static CURRENT: RefCell<Vec<&'static TestGroup>> = const { RefCell::new(Vec::new()) };
+}
+
+impl TestGroup {
+    pub const fn new(size: usize) -> Self {
+        Self {
+            size,
+            state: Mutex::new(None),
+            release_at_exit: Once::new(),
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, Option<GroupState>> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Every test enter in its group before resolve its fixtures and leave it when the
+    /// returned guard is dropped.
+    pub fn enter(&'static self) -> TestGroupGuard {
+        self.release_at_exit
+            .call_once(|| crate::once::at_exit(move || self.release()));
+        self.state().get_or_insert_with(|| GroupState {
+            remaining: self.size,
+            cells: Default::default(),
+        });
+        CURRENT.with(|current| current.borrow_mut().push(self));
+        TestGroupGuard(self)
+    }
+
+    fn leave(&self) {
+        let released = {
+            let mut state = self.state();
+            match state.as_mut() {
+                Some(s) if s.remaining > 1 => {
+                    s.remaining -= 1;
+                    None
+                }
+                _ => state.take(),
+            }
+        };
+        // Drop scoped values without hold the lock
+        drop(released)
+    }
+
+    fn release(&self) {
+        let released = self.state().take();
+        drop(released)
+    }
+
+    fn cell<K: 'static, C: Default + Send + Sync + 'static>(&self) -> Arc<C> {
+        let cell = self
+            .state()
+            .get_or_insert_with(Default::default)
+            .cells
+            .entry(TypeId::of::<(K, C)>())
+            .or_insert_with(|| Arc::new(C::default()))
+            .clone();
+        cell.downcast().unwrap()
+    }
+}
+
+#[doc(hidden)]
+pub struct TestGroupGuard(&'static TestGroup);
+
+impl Drop for TestGroupGuard {
+    fn drop(&mut self) {
+        let _ = CURRENT.try_with(|current| current.borrow_mut().pop());
+        self.0.leave();
+    }
+}
+
+fn current_cell<K: 'static, C: Default + Send + Sync + 'static>() -> Option<Arc<C>> {
+    CURRENT
+        .with(|current| current.borrow().last().copied())
+        .map(|group| group.cell::<K, C>())
+}
+
+/// Return the value of the fixture `K` shared by the tests in the current group or a
+/// new one if the fixture is not used in an `#[rstest]` generated test.
+#[doc(hidden)]
+pub fn get_or_init<K: 'static, T: Send + Sync + 'static, F: FnOnce() -> T>(init: F) -> Shared<T> {
+    match current_cell::<K, OnceLock<Shared<T>>>() {
+        Some(cell) => cell.get_or_init(|| Shared::new(init())).clone(),
+        None => Shared::new(init()),
+    }
+}
+
+/// Like [`get_or_init()`] but for `async` fixtures.
+#[doc(hidden)]
+pub async fn get_or_init_async<K: 'static, T: Send + Sync + 'static, F: Future<Output = T>>(
+    init: F,
+) -> Shared<T> {
+    match current_cell::<K, AsyncOnceCell<Shared<T>>>() {
+        Some(cell) => cell
+            .get_or_init(async move { Shared::new(init.await) })
+            .await
+            .clone(),
+        None => Shared::new(init.await),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Fixture;
+
+    struct Counted(&'static AtomicUsize);
+
+    impl Drop for Counted {
+        fn drop(&mut self) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn should_share_value_in_the_group_and_drop_it_when_last_test_leave() {
+        static GROUP: TestGroup = TestGroup::new(3);
+        static INIT: AtomicUsize = AtomicUsize::new(0);
+        static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+        let test = || {
+            let _guard = GROUP.enter();
+            get_or_init::<Fixture, _, _>(|| {
+                INIT.fetch_add(1, Ordering::SeqCst);
+                Counted(&DROPPED)
+            })
+        };
+
+        let first = test();
+        let second = test();
+        assert!(Arc::ptr_eq(&first.0, &second.0));
+        drop((first, second));
+        assert_eq!(0, DROPPED.load(Ordering::SeqCst));
+
+        drop(test());
+
+        assert_eq!(1, INIT.load(Ordering::SeqCst));
+        assert_eq!(1, DROPPED.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn should_release_the_values_of_a_group_that_never_completed() {
+        static GROUP: TestGroup = TestGroup::new(2);
+        static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+        drop({
+            let _guard = GROUP.enter();
+            get_or_init::<Fixture, _, _>(|| Counted(&DROPPED))
+        });
+        assert_eq!(0, DROPPED.load(Ordering::SeqCst));
+
+        GROUP.release();
+
+        assert_eq!(1, DROPPED.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn should_use_a_value_for_each_fixture_and_type() {
+        static GROUP: TestGroup = TestGroup::new(1);
+        struct Other;
+
+        let _guard = GROUP.enter();
+
+        assert_eq!(1, *get_or_init::<Fixture, _, _>(|| 1_u32));
+        assert_eq!(1, *get_or_init::<Fixture, _, _>(|| 2_u32));
+        assert_eq!(3, *get_or_init::<Other, _, _>(|| 3_u32));
+        assert_eq!("4", *get_or_init::<Fixture, _, _>(|| "4"));
+    }
+
+    #[test]
+    fn should_create_a_new_value_outside_a_group() {
+        assert_eq!(1, *get_or_init::<Fixture, _, _>(|| 1_u32));
+        assert_eq!(2, *get_or_init::<Fixture, _, _>(|| 2_u32));
+    }
+
+    #[async_std::test]
+    async fn should_share_async_values_in_the_group() {
+        static GROUP: TestGroup = TestGroup::new(2);
+
+        let _guard = GROUP.enter();
+
+        assert_eq!(
+            42,
+            *get_or_init_async::<Fixture, _, _>(async { 42_u32 }).await
+        );
+        assert_eq!(
+            42,
+            *get_or_init_async::<Fixture, _, _>(async { 0_u32 }).await
+        );
+    }
+}
@@ -0,0 +1,564 @@
+//! Property based arguments: an argument annotated by `#[strategy(expr)]` is generated
+//! by the [`Strategy`] `expr` and the test is executed for many random values. When a
+//! value makes the test fail `rstest` shrinks it to a minimal failing one and print the
+//! seed that reproduces the failure.
+//!
+//! The number of iterations is `100` and can be changed by `RSTEST_ITERATIONS`
+//! environment variable; `RSTEST_SEED` fix the seed used to generate the values.
+use std::{
+    collections::hash_map::RandomState,
+    fmt::Debug,
+    hash::{BuildHasher, Hash, Hasher},
+    ops::{Range, RangeInclusive},
+    panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
+};
+
+/// The environment variable that fix the seed used to generate the values.
+pub const SEED_ENV: &str = "RSTEST_SEED";
+/// The environment variable that set how many values should be tested.
+pub const ITERATIONS_ENV: &str = "RSTEST_ITERATIONS";
+
+const DEFAULT_ITERATIONS: usize = 100;
+const MAX_SHRINK_STEPS: usize = 1024;
+
+/// A small and fast pseudo random numbers generator (splitmix64): the same seed
+/// generate always the same values on every platform.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A value in `0..n`, `n` should not be zero.
+    pub fn below(&mut self, n: u64) -> u64 {
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+
+    pub fn gen_bool(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+
+    /// A value in `[0, 1)`.
+    pub fn gen_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn between(&mut self, low: i128, high: i128) -> i128 {
+        let span = (high - low) as u128 + 1;
+        if span > u64::MAX as u128 {
+            low + self.next_u64() as i128
+        } else {
+            low + self.below(span as u64) as i128
+        }
+    }
+}
+
+/// How to generate the values of a `#[strategy]` argument and how to shrink a
+/// failing value.
+pub trait Strategy {
+    type Value: Clone + Debug;
+
+    fn generate(&self, rng: &mut Rng) -> Self::Value;
+
+    /// The simpler values to try when `value` makes the test fail: the simplest
+    /// first. The default implementation doesn't shrink at all.
+    fn shrink(&self, _value: &Self::Value) -> Vec<Self::Value> {
+        Vec::new()
+    }
+}
+
+/// The candidates between `target` and `value`: `target` first and then closer and
+/// closer to `value`.
+fn shrink_towards(value: i128, target: i128) -> Vec<i128> {
+    if value == target {
+        return Vec::new();
+    }
+    let mut candidates = vec![target];
+    let mut delta = (value - target) / 2;
+    while delta != 0 {
+        candidates.push(value - delta);
+        delta /= 2;
+    }
+    candidates.dedup();
+    candidates
+}
+
+/// Shrink towards zero or towards the range bound closer to it.
+fn shrink_in(value: i128, low: i128, high: i128) -> Vec<i128> {
+    shrink_towards(value, 0.max(low).min(high))
+}
+
+macro_rules! integer_strategies {
+    ($($t:ty),*) => {
+        $(
+            impl Strategy for Range<$t> {
+                type Value = $t;
+
+                fn generate(&self, rng: &mut Rng) -> $t {
+                    assert!(self.start < self.end, "Empty range {:?}", self);
+                    rng.between(self.start as i128, self.end as i128 - 1) as $t
+                }
+
+                fn shrink(&self, value: &$t) -> Vec<$t> {
+                    shrink_in(*value as i128, self.start as i128, self.end as i128 - 1)
+                        .into_iter()
+                        .map(|v| v as $t)
+                        .collect()
+                }
+            }
+
+            impl Strategy for RangeInclusive<$t> {
+                type Value = $t;
+
+                fn generate(&self, rng: &mut Rng) -> $t {
+                    assert!(self.start() <= self.end(), "Empty range {:?}", self);
+                    rng.between(*self.start() as i128, *self.end() as i128) as $t
+                }
+
+                fn shrink(&self, value: &$t) -> Vec<$t> {
+                    shrink_in(*value as i128, *self.start() as i128, *self.end() as i128)
+                        .into_iter()
+                        .map(|v| v as $t)
+                        .collect()
+                }
+            }
+
+            impl Arbitrary for $t {
+                fn arbitrary(rng: &mut Rng) -> $t {
+                    rng.between(<$t>::MIN as i128, <$t>::MAX as i128) as $t
+                }
+
+                fn shrink(&self) -> Vec<$t> {
+                    shrink_towards(*self as i128, 0)
+                        .into_iter()
+                        .map(|v| v as $t)
+                        .collect()
+                }
+            }
+        )*
+    };
+}
+
+integer_strategies!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+impl Strategy for Range<f64> {
+    type Value = f64;
+
+    fn generate(&self, rng: &mut Rng) -> f64 {
+        self.start + rng.gen_f64() * (self.end - self.start)
+    }
+
+    fn shrink(&self, value: &f64) -> Vec<f64> {
+        let target = 0.0f64.max(self.start).min(self.end);
+        if self.contains(&target) && *value != target {
+            vec![target, value.trunc()]
+                .into_iter()
+                .filter(|v| v != value && self.contains(v))
+                .collect()
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+/// The types that have a natural way to generate every value: use them by [`any()`].
+pub trait Arbitrary: Clone + Debug + Sized {
+    fn arbitrary(rng: &mut Rng) -> Self;
+
+    fn shrink(&self) -> Vec<Self> {
+        Vec::new()
+    }
+}
+
+impl Arbitrary for bool {
+    fn arbitrary(rng: &mut Rng) -> bool {
+        rng.gen_bool()
+    }
+
+    fn shrink(&self) -> Vec<bool> {
+        if *self {
+            vec![false]
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+impl Arbitrary for char {
+    /// Printable ascii chars
+    fn arbitrary(rng: &mut Rng) -> char {
+        (b' ' + rng.below(95) as u8) as char
+    }
+
+    fn shrink(&self) -> Vec<char> {
+        match *self {
+            'a' => Vec::new(),
+            _ => vec!['a'],
+        }
+    }
+}
+
+/// The strategy returned by [`any()`].
+#[derive(Debug)]
+pub struct Any<T>(std::marker::PhantomData<T>);
+
+/// Every value of `T`: `#[strategy(any::<u32>())]`.
+pub fn any<T: Arbitrary>() -> Any<T> {
+    Any(std::marker::PhantomData)
+}
+
+impl<T: Arbitrary> Strategy for Any<T> {
+    type Value = T;
+
+    fn generate(&self, rng: &mut Rng) -> T {
+        T::arbitrary(rng)
+    }
+
+    fn shrink(&self, value: &T) -> Vec<T> {
+        value.shrink()
+    }
+}
+
+/// Always the same value.
+#[derive(Debug, Clone)]
+pub struct Just<T>(pub T);
+
+impl<T: Clone + Debug> Strategy for Just<T> {
+    type Value = T;
+
+    fn generate(&self, _rng: &mut Rng) -> T {
+        self.0.clone()
+    }
+}
+
+/// The strategy returned by [`from_fn()`].
+pub struct FromFn<F>(F);
+
+/// Generate the values by a function: these values are not shrunk.
+pub fn from_fn<T: Clone + Debug, F: Fn(&mut Rng) -> T>(f: F) -> FromFn<F> {
+    FromFn(f)
+}
+
+impl<T: Clone + Debug, F: Fn(&mut Rng) -> T> Strategy for FromFn<F> {
+    type Value = T;
+
+    fn generate(&self, rng: &mut Rng) -> T {
+        (self.0)(rng)
+    }
+}
+
+/// The strategy returned by [`vec()`].
+#[derive(Debug, Clone)]
+pub struct VecStrategy<S> {
+    element: S,
+    len: Range<usize>,
+}
+
+/// Vectors of `len` elements generated by `element`.
+pub fn vec<S: Strategy>(element: S, len: Range<usize>) -> VecStrategy<S> {
+    VecStrategy { element, len }
+}
+
+impl<S: Strategy> Strategy for VecStrategy<S> {
+    type Value = Vec<S::Value>;
+
+    fn generate(&self, rng: &mut Rng) -> Self::Value {
+        let len = self.len.generate(rng);
+        (0..len).map(|_| self.element.generate(rng)).collect()
+    }
+
+    /// Try to truncate the vector to the minimal length, then to remove an element and
+    /// at last to shrink every element.
+    fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+        let min = self.len.start;
+        let mut candidates = Vec::new();
+        if value.len() <= min {
+            return self.shrink_elements(value);
+        }
+        if min + 1 < value.len() {
+            candidates.push(value[..min].to_vec());
+        }
+        for i in 0..value.len() {
+            let mut v = value.clone();
+            v.remove(i);
+            candidates.push(v);
+        }
+        candidates.extend(self.shrink_elements(value));
+        candidates
+    }
+}
+
+impl<S: Strategy> VecStrategy<S> {
+    fn shrink_elements(&self, value: &[S::Value]) -> Vec<Vec<S::Value>> {
+        value
+            .iter()
+            .enumerate()
+            .flat_map(|(i, e)| {
+                self.element.shrink(e).into_iter().map(move |s| {
+                    let mut v = value.to_vec();
+                    v[i] = s;
+                    v
+                })
+            })
+            .collect()
+    }
+}
+
+macro_rules! tuple_strategies {
+    ($(($($s:ident $v:ident $i:tt),+)),*) => {
+        $(
+            impl<$($s: Strategy),+> Strategy for ($($s,)+) {
+                type Value = ($($s::Value,)+);
+
+                fn generate(&self, rng: &mut Rng) -> Self::Value {
+                    ($(self.$i.generate(rng),)+)
+                }
+
+                fn shrink(&self, value: &Self::Value) -> Vec<Self::Value> {
+                    let mut candidates = Vec::new();
+                    $(
+                        for $v in self.$i.shrink(&value.$i) {
+                            let mut c = value.clone();
+                            c.$i = $v;
+                            candidates.push(c);
+                        }
+                    )+
+                    candidates
+                }
+            }
+        )*
+    };
+}
+
+tuple_strategies!(
+    (A a 0),
+    (A a 0, B b 1),
+    (A a 0, B b 1, C c 2),
+    (A a 0, B b 1, C c 2, D d 3),
+    (A a 0, B b 1, C c 2, D d 3, E e 4),
+    (A a 0, B b 1, C c 2, D d 3, E e 4, F f 5),
+    (A a 0, B b 1, C c 2, D d 3, E e 4, F f 5, G g 6),
+    (A a 0, B b 1, C c 2, D d 3, E e 4, F f 5, G g 6, H h 7)
+);
+
+/// What a test returns: a test fails if it panics or if its outcome is a failure.
+pub trait Outcome {
+    fn is_failure(&self) -> bool;
+}
+
+impl Outcome for () {
+    fn is_failure(&self) -> bool {
+        false
+    }
+}
+
+impl<T, E> Outcome for Result<T, E> {
+    fn is_failure(&self) -> bool {
+        self.is_err()
+    }
+}
+
+enum Failure<R> {
+    Returned(R),
+    Panicked(Box<dyn std::any::Any + Send>),
+}
+
+impl<R> Failure<R> {
+    fn raise(self) -> R {
+        match self {
+            Failure::Returned(outcome) => outcome,
+            Failure::Panicked(payload) => resume_unwind(payload),
+        }
+    }
+}
+
+fn execute<T, R: Outcome>(test: &impl Fn(T) -> R, value: T) -> Result<R, Failure<R>> {
+    match catch_unwind(AssertUnwindSafe(|| test(value))) {
+        Ok(outcome) if !outcome.is_failure() => Ok(outcome),
+        Ok(outcome) => Err(Failure::Returned(outcome)),
+        Err(payload) => Err(Failure::Panicked(payload)),
+    }
+}
+
+fn env_var<T: std::str::FromStr>(name: &str) -> Option<T> {
+    let value = std::env::var(name).ok()?;
+    match value.trim().parse() {
+        Ok(v) => Some(v),
+        Err(_) => panic!("Invalid {} value '{}'", name, value),
+    }
+}
+
+fn random_seed() -> u64 {
+    let mut hasher = RandomState::new().build_hasher();
+    std::time::SystemTime::now().hash(&mut hasher);
+    std::thread::current().id().hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Run `test` for the values generated by `strategy` and return the outcome of the
+/// last one. If a value makes the test fail, shrink it, print the minimal failing value
+/// with the seed that reproduce the failure, and fail with the minimal value's outcome.
+#[doc(hidden)]
+pub fn check<S: Strategy, R: Outcome>(name: &str, strategy: S, test: impl Fn(S::Value) -> R) -> R {
+    let seed = env_var(SEED_ENV).unwrap_or_else(random_seed);
+    let iterations = env_var(ITERATIONS_ENV).unwrap_or(DEFAULT_ITERATIONS).max(1);
+    let mut rng = Rng::new(seed);
+    let mut last = None;
+    for iteration in 1..=iterations {
+        let value = strategy.generate(&mut rng);
+        match execute(&test, value.clone()) {
+            Ok(outcome) => last = Some(outcome),
+            Err(failure) => {
+                let (value, failure) = shrink(&strategy, &test, value, failure);
+                println!("{:-^40}", " PROPERTY FAILED ");
+                println!("test = {}", name);
+                println!("iteration = {}/{}", iteration, iterations);
+                println!("minimal failing input = {:?}", value);
+                println!("seed = {} (rerun with {}={})", seed, SEED_ENV, seed);
+                return failure.raise();
+            }
+        }
+    }
+    last.expect("At least one iteration")
+}
+
+fn shrink<S: Strategy, R: Outcome>(
+    strategy: &S,
+    test: &impl Fn(S::Value) -> R,
+    mut value: S::Value,
+    mut failure: Failure<R>,
+) -> (S::Value, Failure<R>) {
+    let mut steps = 0;
+    'shrink: while steps < MAX_SHRINK_STEPS {
+        for candidate in strategy.shrink(&value) {
+            steps += 1;
+            if let Err(f) = execute(test, candidate.clone()) {
+                value = candidate;
+                failure = f;
+                continue 'shrink;
+            }
+            if steps >= MAX_SHRINK_STEPS {
+                break;
+            }
+        }
+        break;
+    }
+    (value, failure)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rng_should_be_deterministic() {
+        let values = |seed| {
+            let mut rng = Rng::new(seed);
+            (0..10).map(|_| rng.next_u64()).collect::<Vec<_>>()
+        };
+
+        assert_eq!(values(42), values(42));
+        assert_ne!(values(42), values(43));
+    }
+
+    #[test]
+    fn ranges_should_generate_values_in_range() {
+        let mut rng = Rng::new(0);
+
+        for _ in 0..1000 {
+            assert!((-3..5).contains(&(-3..5).generate(&mut rng)));
+            assert!((250..=255u8).contains(&(250..=255u8).generate(&mut rng)));
+            assert!((0.5..1.5).contains(&(0.5..1.5).generate(&mut rng)));
+        }
+        let all = (0..1000)
+            .map(|_| (0..=3u8).generate(&mut rng))
+            .collect::<std::collections::BTreeSet<_>>();
+        assert_eq!(vec![0, 1, 2, 3], all.into_iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn full_range_should_not_overflow() {
+        let mut rng = Rng::new(0);
+
+        for _ in 0..100 {
+            any::<u64>().generate(&mut rng);
+            any::<i64>().generate(&mut rng);
+            (i64::MIN..=i64::MAX).generate(&mut rng);
+        }
+    }
+
+    #[test]
+    fn integers_should_shrink_towards_zero_or_the_closer_bound() {
+        assert_eq!(vec![0, 50, 75, 88, 94, 97, 99], (0..200).shrink(&100));
+        assert_eq!(vec![0, -2, -3], any::<i32>().shrink(&-4));
+        assert_eq!(vec![10, 11], (10..20).shrink(&12));
+        assert_eq!(Vec::<i32>::new(), (10..20).shrink(&10));
+        assert_eq!(vec![-10, -11], (-20..=-10).shrink(&-12));
+    }
+
+    #[test]
+    fn vec_should_respect_len() {
+        let mut rng = Rng::new(0);
+        let strategy = vec(0..10u8, 2..5);
+
+        for _ in 0..100 {
+            let v = strategy.generate(&mut rng);
+            assert!((2..5).contains(&v.len()));
+        }
+        assert!(strategy
+            .shrink(&std::vec![1, 2, 3, 4])
+            .iter()
+            .all(|v| v.len() >= 2));
+    }
+
+    #[test]
+    fn check_should_return_the_last_outcome_if_all_pass() {
+        let result: Result<u32, ()> = check("pass", (0..10u32,), |(v,)| Ok(v));
+
+        assert!(result.unwrap() < 10);
+    }
+
+    #[test]
+    fn check_should_return_the_minimal_failing_outcome() {
+        let result = check("fail", (any::<u32>(), any::<bool>()), |(v, b)| {
+            if v >= 1000 {
+                Err((v, b))
+            } else {
+                Ok(())
+            }
+        });
+
+        assert_eq!(Err((1000, false)), result);
+    }
+
+    #[test]
+    fn check_should_shrink_vectors() {
+        let result = check("fail", (vec(0..100u8, 0..20),), |(v,)| {
+            if v.iter().any(|&e| e > 50) {
+                Err(v)
+            } else {
+                Ok(())
+            }
+        });
+
+        assert_eq!(Err(std::vec![51]), result);
+    }
+
+    #[test]
+    #[should_panic(expected = "too big")]
+    fn check_should_resume_the_minimal_panic() {
+        check("panic", (0..1000u32,), |(v,)| assert!(v < 500, "too big"));
+    }
+}
@@ -0,0 +1,392 @@
+//! Fixture teardown support: the code given in `#[teardown(...)]` fixture attribute
+//! should return something that implement [`TearDown`] and will be executed
+//! after the test that used the fixture is terminated, also if it panics.
+//!
+//! The guards are tracked by thread: a guard registered by another thread than the one
+//! that opened the test's [`TearDownScope`] is not executed by the scope but when its
+//! thread terminates.
+use std::{
+    any::Any,
+    cell::RefCell,
+    panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+/// The teardown contract: `tear_down()` consume the guard and release the resources.
+/// Every `FnOnce()` closure is a teardown guard.
+pub trait TearDown {
+    fn tear_down(self);
+}
+
+impl<F: FnOnce()> TearDown for F {
+    fn tear_down(self) {
+        self()
+    }
+}
+
+impl<G: TearDown> TearDown for Option<G> {
+    fn tear_down(self) {
+        if let Some(g) = self {
+            g.tear_down()
+        }
+    }
+}
+
+impl<A: TearDown, B: TearDown> TearDown for (A, B) {
+    fn tear_down(self) {
+        self.0.tear_down();
+        self.1.tear_down();
+    }
+}
+
+type Guard = Box<dyn FnOnce()>;
+
+/// A registered guard: `release` guards just release a lock and are executed also when
+/// the scope's teardowns are forgotten.
+struct Registered {
+    guard: Guard,
+    release: bool,
+}
+
+#[derive(Default)]
+struct GuardsStack {
+    guards: Vec<Registered>,
+    /// The depth of every open scope
+    scopes: Vec<usize>,
+}
+
+impl GuardsStack {
+    fn unwind_to(&mut self, depth: usize) -> Vec<Registered> {
+        let depth = depth.min(self.guards.len());
+        let mut guards = self.guards.split_off(depth);
+        guards.reverse();
+        guards
+    }
+}
+
+impl Drop for GuardsStack {
+    fn drop(&mut self) {
+        // Thread is terminating: release what is not released by a scope yet
+        run_all(self.unwind_to(0));
+    }
+}
+
+thread_local! {
+    static GUARDS: RefCell<GuardsStack> = RefCell::new(GuardsStack::default());
+    static RELEASES: RefCell<Option<Releases>> = const { RefCell::new(None) };
+}
+
+type Release = Arc<Mutex<Option<Box<dyn Any + Send>>>>;
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// The release guards registered by a thread that runs a test's code: if the thread never
+/// stops the test's thread can release them anyway.
+#[derive(Clone, Default)]
+pub(crate) struct Releases(Arc<Mutex<Vec<Release>>>);
+
+impl Releases {
+    /// Track the release guards that the current thread will register.
+    pub(crate) fn track(&self) {
+        RELEASES.with(|releases| *releases.borrow_mut() = Some(self.clone()));
+    }
+
+    /// Drop all the tracked guards that are not released yet.
+    pub(crate) fn release_all(&self) {
+        let releases = std::mem::take(&mut *lock(&self.0));
+        for release in releases {
+            let guard = lock(&release).take();
+            drop(guard)
+        }
+    }
+}
+
+/// Execute all guards also if some of them panic and resume the first panic
+/// (if any and we are not already panicking)
+fn run_all(guards: Vec<Registered>) {
+    let mut first_panic: Option<Box<dyn Any + Send>> = None;
+    for Registered { guard, .. } in guards {
+        if let Err(payload) = catch_unwind(AssertUnwindSafe(guard)) {
+            first_panic.get_or_insert(payload);
+        }
+    }
+    if let Some(payload) = first_panic {
+        if !std::thread::panicking() {
+            resume_unwind(payload)
+        }
+    }
+}
+
+/// Register the teardown guard returned by `teardown` applied to the fixture `value`. Guards
+/// are stacked in the current thread and executed in reverse order when the enclosing
+/// [`TearDownScope`] is dropped.
+#[doc(hidden)]
+pub fn register<T: ?Sized, G: TearDown + 'static, F: FnOnce(&T) -> G>(value: &T, teardown: F) {
+    let guard = teardown(value);
+    push(Box::new(move || guard.tear_down()), false);
+}
+
+/// Register a guard that release a lock held by the test (like the `#[once(reset)]` fixtures
+/// access) by dropping it: unlike the teardown guards it's dropped also when the
+/// scope is forgotten or the thread that runs the test never stops, otherwise all the tests
+/// that wait for the lock would hang forever.
+#[doc(hidden)]
+pub fn register_release<G: Send + 'static>(guard: G) {
+    let release: Release = Arc::new(Mutex::new(Some(Box::new(guard))));
+    RELEASES.with(|releases| {
+        if let Some(releases) = releases.borrow().as_ref() {
+            lock(&releases.0).push(release.clone())
+        }
+    });
+    push(
+        Box::new(move || {
+            let guard = lock(&release).take();
+            drop(guard)
+        }),
+        true,
+    );
+}
+
+fn push(guard: Guard, release: bool) {
+    GUARDS.with(|stack| {
+        stack
+            .borrow_mut()
+            .guards
+            .push(Registered { guard, release })
+    });
+}
+
+/// Every test rendered by `rstest` open a scope before resolve its fixtures: when the scope
+/// is dropped all teardown guards registered after it was created are executed
+/// in reverse order.
+#[doc(hidden)]
+pub struct TearDownScope {
+    depth: usize,
+}
+
+impl TearDownScope {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        let depth = GUARDS.with(|stack| {
+            let mut stack = stack.borrow_mut();
+            let depth = stack.guards.len();
+            stack.scopes.push(depth);
+            depth
+        });
+        Self { depth }
+    }
+}
+
+impl Drop for TearDownScope {
+    fn drop(&mut self) {
+        let guards = GUARDS
+            .try_with(|stack| {
+                let mut stack = stack.borrow_mut();
+                stack.scopes.pop();
+                stack.unwind_to(self.depth)
+            })
+            .unwrap_or_default();
+        run_all(guards);
+    }
+}
+
+/// Forget the teardown guards of the innermost scope without execute them: used when a
+/// timed out test thread is still running and could still use its fixtures. The release
+/// guards are executed anyway. Return how many guards are forgotten.
+pub(crate) fn forget_scope() -> usize {
+    let (releases, forgotten): (Vec<_>, Vec<_>) = GUARDS.with(|stack| {
+        let mut stack = stack.borrow_mut();
+        match stack.scopes.last().copied() {
+            Some(depth) => stack.unwind_to(depth),
+            None => vec![],
+        }
+        .into_iter()
+        .partition(|registered| registered.release)
+    });
+    let count = forgotten.len();
+    std::mem::forget(forgotten);
+    run_all(releases);
+    count
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::{cell::RefCell, rc::Rc};
+
+    type Trace = Rc<RefCell<Vec<&'static str>>>;
+
+    fn trace() -> (Trace, impl Fn(&'static str) -> Box<dyn FnOnce()>) {
+        let trace = Rc::new(RefCell::new(vec![]));
+        let t = trace.clone();
+        (trace, move |msg| {
+            let t = t.clone();
+            Box::new(move || t.borrow_mut().push(msg))
+        })
+    }
+
+    #[test]
+    fn should_run_guards_in_reverse_order_when_scope_is_dropped() {
+        let (trace, push) = trace();
+        {
+            let _scope = TearDownScope::new();
+            register(&(), |_| push("first"));
+            register(&(), |_| push("second"));
+            register(&(), |_| (push("third"), push("fourth")));
+            assert!(trace.borrow().is_empty());
+        }
+
+        assert_eq!(vec!["third", "fourth", "second", "first"], *trace.borrow());
+    }
+
+    #[test]
+    fn should_run_just_the_guards_registered_in_scope() {
+        let (trace, push) = trace();
+        let _outer = TearDownScope::new();
+        register(&(), |_| push("outer"));
+        {
+            let _scope = TearDownScope::new();
+            register(&(), |_| push("inner"));
+        }
+
+        assert_eq!(vec!["inner"], *trace.borrow());
+    }
+
+    #[test]
+    fn should_pass_fixture_value_to_teardown() {
+        let trace = Rc::new(RefCell::new(vec![]));
+        {
+            let _scope = TearDownScope::new();
+            let t = trace.clone();
+            register(&42, move |v| {
+                let v = *v;
+                move || t.borrow_mut().push(v)
+            });
+        }
+
+        assert_eq!(vec![42], *trace.borrow());
+    }
+
+    #[test]
+    fn should_run_all_guards_when_scope_is_dropped_by_panic() {
+        let (trace, push) = trace();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let _scope = TearDownScope::new();
+            register(&(), |_| push("first"));
+            register(&(), |_| -> Box<dyn FnOnce()> {
+                Box::new(|| panic!("guard"))
+            });
+            register(&(), |_| push("last"));
+            panic!("test")
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(vec!["last", "first"], *trace.borrow());
+    }
+
+    #[test]
+    fn should_forget_the_guards_of_the_innermost_scope() {
+        let (trace, push) = trace();
+        {
+            let _outer = TearDownScope::new();
+            register(&(), |_| push("outer"));
+            {
+                let _scope = TearDownScope::new();
+                register(&(), |_| push("first"));
+                register(&(), |_| push("second"));
+
+                assert_eq!(2, forget_scope());
+            }
+            assert!(trace.borrow().is_empty());
+        }
+
+        assert_eq!(vec!["outer"], *trace.borrow());
+    }
+
+    #[test]
+    fn should_run_the_guards_registered_by_another_thread_when_it_terminates() {
+        let torn_down = Arc::new(Mutex::new(Vec::new()));
+        let (registered, wait) = std::sync::mpsc::channel();
+        let (stop, stopped) = std::sync::mpsc::channel::<()>();
+        let scope = TearDownScope::new();
+        let thread_torn_down = torn_down.clone();
+        let thread = std::thread::spawn(move || {
+            register(&(), |_| move || lock(&thread_torn_down).push("thread"));
+            registered.send(()).unwrap();
+            let _ = stopped.recv();
+        });
+        wait.recv().unwrap();
+
+        drop(scope);
+        assert!(lock(&torn_down).is_empty());
+
+        drop(stop);
+        thread.join().unwrap();
+        assert_eq!(vec!["thread"], *lock(&torn_down));
+    }
+
+    struct Release(Arc<Mutex<u32>>);
+
+    impl Drop for Release {
+        fn drop(&mut self) {
+            *lock(&self.0) += 1
+        }
+    }
+
+    #[test]
+    fn should_release_also_when_forget_the_scope() {
+        let (trace, push) = trace();
+        let released = Arc::new(Mutex::new(0));
+        {
+            let _scope = TearDownScope::new();
+            register(&(), |_| push("teardown"));
+            register_release(Release(released.clone()));
+
+            assert_eq!(1, forget_scope());
+            assert_eq!(1, *lock(&released));
+        }
+
+        assert!(trace.borrow().is_empty());
+        assert_eq!(1, *lock(&released));
+    }
+
+    #[test]
+    fn should_release_the_guards_of_a_thread_that_never_stops() {
+        let released = Arc::new(Mutex::new(0));
+        let releases = Releases::default();
+        let (registered, wait) = std::sync::mpsc::channel();
+        let thread_releases = releases.clone();
+        let thread_released = released.clone();
+        std::thread::spawn(move || {
+            thread_releases.track();
+            let _scope = TearDownScope::new();
+            register_release(Release(thread_released));
+            registered.send(()).unwrap();
+            loop {
+                std::thread::park();
+            }
+        });
+        wait.recv().unwrap();
+
+        releases.release_all();
+
+        assert_eq!(1, *lock(&released));
+    }
+
+    #[test]
+    fn should_resume_guard_panic_after_all_guards_are_executed() {
+        let (trace, push) = trace();
+        let result = catch_unwind(AssertUnwindSafe(|| {
+            let _scope = TearDownScope::new();
+            register(&(), |_| push("first"));
+            register(&(), |_| -> Box<dyn FnOnce()> {
+                Box::new(|| panic!("guard"))
+            });
+        }));
+
+        assert!(result.is_err());
+        assert_eq!(vec!["first"], *trace.borrow());
+    }
+}
@@ -1,37 +1,416 @@
-use std::{sync::mpsc, time::Duration};
+//! Timeouts for `#[timeout(duration)]` tests.
+//!
+//! Sync tests are executed in a new thread: Rust cannot kill a thread, so when the
+//! timeout expires the test thread is *cancelled* and stops at the next
+//! [`checkpoint()`] (or [`sleep()`]) by unwinding, and its arguments and fixtures are
+//! dropped. Long running sync tests should call [`checkpoint()`] from time to time.
+//!
+//! Async tests are dropped when the timeout expires: if the test is running in a tokio
+//! runtime with the timer enabled and the `tokio-timeout` feature is enabled the runtime's
+//! timer is used, otherwise a runtime agnostic timer.
+//!
+//! `#[timeout(duration, mode = watchdog)]` sync tests resolve their fixtures in the new
+//! thread too, so their arguments and fixtures don't need to be `Send`. When the timeout
+//! expires the test is cancelled and, if it doesn't stop, it fails anyway and its thread
+//! is leaked: the test's fixtures are never torn down but the other tests can go on.
+//!
+//! The tests without `#[timeout]` use the default timeout given by `RSTEST_TIMEOUT`
+//! environment variable (like `RSTEST_TIMEOUT=30s`), if any: `#[no_timeout]` opts
+//! out. Sync tests with the default timeout run like the watchdog ones, so a hung test
+//! cannot block forever.
+use std::{
+    backtrace::Backtrace,
+    cell::RefCell,
+    fmt::Display,
+    panic::{catch_unwind, resume_unwind, AssertUnwindSafe},
+    process::{ExitCode, Termination},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc, Mutex, OnceLock,
+    },
+    time::{Duration, Instant},
+};
+
+use crate::{context, strategy::Outcome, teardown::Releases};
 
 #[cfg(feature = "async-timeout")]
 use futures::{select, Future, FutureExt};
 #[cfg(feature = "async-timeout")]
 use futures_timer::Delay;
 
-pub fn execute_with_timeout_sync<T: 'static + Send, F: Fn() -> T + Send + 'static>(
+/// How long a timed out test wait for its thread to stop after cancel it.
+const CANCEL_GRACE: Duration = Duration::from_millis(100);
+
+/// The environment variable that define the default timeout.
+pub const TIMEOUT_ENV: &str = "RSTEST_TIMEOUT";
+
+/// The panic payload used to unwind a cancelled test thread.
+#[derive(Debug)]
+pub struct Cancelled;
+
+/// The cancellation shared by a timed out test and its timeout.
+#[derive(Default)]
+struct Cancellation {
+    cancelled: AtomicBool,
+    /// Where the test stopped
+    stopped_at: Mutex<Option<String>>,
+}
+
+impl Cancellation {
+    fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst)
+    }
+
+    fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    fn stopped_at(&self) -> Option<String> {
+        self.stopped_at.lock().ok().and_then(|mut b| b.take())
+    }
+}
+
+thread_local! {
+    static CANCELLATION: RefCell<Option<Arc<Cancellation>>> = const { RefCell::new(None) };
+}
+
+/// Is the test running in the current thread cancelled by its timeout?
+pub fn is_cancelled() -> bool {
+    CANCELLATION.with(|c| {
+        c.borrow()
+            .as_ref()
+            .map(|c| c.is_cancelled())
+            .unwrap_or_default()
+    })
+}
+
+/// Stop the current test by unwinding if its timeout is expired: fixtures and
+/// values owned by the test are dropped. Outside of a `#[timeout]` test it does nothing.
+pub fn checkpoint() {
+    let cancellation = CANCELLATION.with(|c| c.borrow().clone());
+    if let Some(cancellation) = cancellation.filter(|c| c.is_cancelled()) {
+        if let Ok(mut stopped_at) = cancellation.stopped_at.lock() {
+            *stopped_at = Some(Backtrace::force_capture().to_string());
+        }
+        // Don't use panic!(): the failure is already reported by the timeout
+        resume_unwind(Box::new(Cancelled))
+    }
+}
+
+/// Like `std::thread::sleep()` but wake up and stop the test as soon as its timeout
+/// expires.
+pub fn sleep(duration: Duration) {
+    let deadline = Instant::now() + duration;
+    loop {
+        checkpoint();
+        let now = Instant::now();
+        if now >= deadline {
+            return;
+        }
+        std::thread::park_timeout(deadline - now);
+    }
+}
+
+/// What the test was doing when its timeout expired.
+#[derive(Debug, Clone, PartialEq)]
+enum TestState {
+    /// Stopped at a `checkpoint()`: with the backtrace
+    Stopped(String),
+    /// Completed after the timeout without reach any `checkpoint()`
+    Completed,
+    /// Still running: it never reached a `checkpoint()`
+    Running,
+    /// The test's future was dropped
+    #[cfg(feature = "async-timeout")]
+    Dropped,
+}
+
+impl Display for TestState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Stopped(_) => write!(f, "stopped at checkpoint()"),
+            Self::Completed => write!(f, "completed after the timeout (no checkpoint() reached)"),
+            Self::Running => write!(
+                f,
+                "still running (no checkpoint() reached): fixtures teardown skipped"
+            ),
+            #[cfg(feature = "async-timeout")]
+            Self::Dropped => write!(f, "future dropped"),
+        }
+    }
+}
+
+/// The failure message of a timed out test.
+#[derive(Debug, Clone)]
+struct TimeoutReport {
+    test: Option<&'static str>,
+    thread: Option<String>,
+    timeout: Duration,
+    start: Instant,
+}
+
+impl TimeoutReport {
+    /// Collect what we know about the test running in the current thread.
+    fn start(timeout: Duration) -> Self {
+        Self {
+            test: context::current_test(),
+            thread: std::thread::current().name().map(ToOwned::to_owned),
+            timeout,
+            start: Instant::now(),
+        }
+    }
+
+    fn render(&self, state: &TestState) -> String {
+        let mut out = format!("Timeout {:?} expired\n", self.timeout);
+        let mut field = |name: &str, value: &dyn Display| {
+            out.push_str(&format!("    {:<10}{}\n", format!("{}:", name), value))
+        };
+        if let Some(test) = self.test {
+            field("test", &test);
+        }
+        field("timeout", &format!("{:?}", self.timeout));
+        field("elapsed", &format!("{:?}", self.start.elapsed()));
+        if let Some(thread) = &self.thread {
+            field("thread", thread);
+        }
+        field("state", state);
+        // The arguments are traced also by the thread that runs the test
+        let args = context::traced_args();
+        if !args.is_empty() {
+            out.push_str("    arguments:\n");
+            for (name, value) in &args {
+                out.push_str(&format!("        {} = {}\n", name, value));
+            }
+        }
+        if let TestState::Stopped(backtrace) = state {
+            out.push_str("stack backtrace:\n");
+            out.push_str(backtrace);
+        }
+        out
+    }
+
+    fn fail(&self, state: TestState) -> ! {
+        panic!("{}", self.render(&state))
+    }
+}
+
+/// Execute `code` in a new thread that inherits the test's context and wait for it: when
+/// the timeout expires the test is cancelled and, if it doesn't stop in `CANCEL_GRACE`,
+/// the thread is leaked and the test fails anyway. `abandon` is called before fail, when
+/// the thread is still running.
+fn execute_in_worker<T: Send + 'static, F: FnOnce() -> T + Send + 'static>(
     code: F,
     timeout: Duration,
+    abandon: impl FnOnce(),
 ) -> T {
+    let report = TimeoutReport::start(timeout);
     let (sender, receiver) = mpsc::channel();
-    std::thread::spawn(move || sender.send(code()));
-    receiver
-        .recv_timeout(timeout)
-        .unwrap_or_else(|_| panic!("Timeout {:?} expired", timeout))
+    let cancellation = Arc::new(Cancellation::default());
+    let worker_cancellation = cancellation.clone();
+    let context = context::inherit();
+    let releases = Releases::default();
+    let worker_releases = releases.clone();
+    let mut worker = std::thread::Builder::new();
+    if let Some(test) = report.test {
+        worker = worker.name(test.to_owned());
+    }
+    let worker = worker
+        .spawn(move || {
+            let _context = context.map(|context| context.enter());
+            worker_releases.track();
+            CANCELLATION.with(|c| *c.borrow_mut() = Some(worker_cancellation));
+            let _ = sender.send(catch_unwind(AssertUnwindSafe(code)));
+        })
+        .expect("Cannot spawn the test thread");
+    match receiver.recv_timeout(timeout) {
+        Ok(Ok(out)) => out,
+        Ok(Err(payload)) => resume_unwind(payload),
+        Err(_) => {
+            cancellation.cancel();
+            worker.thread().unpark();
+            // Give the test the chance to release its resources before fail
+            let state = match receiver.recv_timeout(CANCEL_GRACE) {
+                Ok(_) => cancellation
+                    .stopped_at()
+                    .map(TestState::Stopped)
+                    .unwrap_or(TestState::Completed),
+                Err(_) => {
+                    // The worker could still use its fixtures: don't tear them down but
+                    // release the locks that the other tests are waiting for
+                    releases.release_all();
+                    abandon();
+                    TestState::Running
+                }
+            };
+            report.fail(state)
+        }
+    }
+}
+
+#[doc(hidden)]
+pub fn execute_with_timeout_sync<T: 'static + Send, F: FnOnce() -> T + Send + 'static>(
+    code: F,
+    timeout: Duration,
+) -> T {
+    // The fixtures are resolved by the test's thread
+    execute_in_worker(code, timeout, || {
+        crate::teardown::forget_scope();
+    })
+}
+
+/// The default timeout given by `RSTEST_TIMEOUT`: a number followed by `ms`, `s`, `m`
+/// or `h` (seconds if omitted).
+pub fn default_timeout() -> Option<Duration> {
+    static DEFAULT: OnceLock<Option<Duration>> = OnceLock::new();
+    *DEFAULT.get_or_init(|| {
+        let value = std::env::var(TIMEOUT_ENV).ok()?;
+        Some(parse_duration(&value).unwrap_or_else(|| {
+            panic!(
+                "Invalid {} value '{}': use something like 30s, 500ms or 2m",
+                TIMEOUT_ENV, value
+            )
+        }))
+    })
+}
+
+fn parse_duration(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+    let amount = amount.parse::<u64>().ok()?;
+    match unit.trim() {
+        "ms" => Some(Duration::from_millis(amount)),
+        "" | "s" => Some(Duration::from_secs(amount)),
+        // An overflow is an invalid value
+        "m" => amount.checked_mul(60).map(Duration::from_secs),
+        "h" => amount.checked_mul(60 * 60).map(Duration::from_secs),
+        _ => None,
+    }
+}
+
+/// The output of a sync test that run under a watchdog or the default timeout: the
+/// test's output if it run in the current thread, or its [`Termination::report()`] if it
+/// run in another thread.
+#[doc(hidden)]
+pub enum TestOutput<T> {
+    Value(T),
+    Report(ExitCode),
+}
+
+impl<T: Termination> Termination for TestOutput<T> {
+    fn report(self) -> ExitCode {
+        match self {
+            Self::Value(value) => value.report(),
+            Self::Report(code) => code,
+        }
+    }
 }
 
+impl<T: Outcome> Outcome for TestOutput<T> {
+    fn is_failure(&self) -> bool {
+        match self {
+            Self::Value(value) => value.is_failure(),
+            Self::Report(code) => *code != ExitCode::SUCCESS,
+        }
+    }
+}
+
+/// Execute the whole test's `code`, fixtures resolution included, in a new thread: so the
+/// test's fixtures and arguments don't need to be `Send`. If the test doesn't stop when the
+/// timeout expires it fails and its thread is leaked.
+#[doc(hidden)]
+pub fn execute_with_watchdog<T: Termination, F: FnOnce() -> T + Send + 'static>(
+    code: F,
+    timeout: Duration,
+) -> TestOutput<T> {
+    TestOutput::Report(execute_in_worker(move || code().report(), timeout, || ()))
+}
+
+/// Execute a sync test without `#[timeout]` under the default timeout, if any.
+#[doc(hidden)]
+pub fn execute_with_default_timeout_sync<T: Termination, F: FnOnce() -> T + Send + 'static>(
+    code: F,
+) -> TestOutput<T> {
+    match default_timeout() {
+        Some(timeout) => execute_with_watchdog(code, timeout),
+        None => TestOutput::Value(code()),
+    }
+}
+
+/// Execute an async test without `#[timeout]` under the default timeout, if any.
 #[cfg(feature = "async-timeout")]
-pub async fn execute_with_timeout_async<T, Fut: Future<Output = T>, F: Fn() -> Fut>(
+#[doc(hidden)]
+pub async fn execute_with_default_timeout_async<T, Fut: Future<Output = T>, F: FnOnce() -> Fut>(
+    code: F,
+) -> T {
+    match default_timeout() {
+        Some(timeout) => execute_with_timeout_async(code, timeout).await,
+        None => code().await,
+    }
+}
+
+#[cfg(feature = "async-timeout")]
+#[doc(hidden)]
+pub async fn execute_with_timeout_async<T, Fut: Future<Output = T>, F: FnOnce() -> Fut>(
     code: F,
     timeout: Duration,
 ) -> T {
+    let report = TimeoutReport::start(timeout);
+    #[cfg(feature = "tokio-timeout")]
+    if let Some(sleep) = tokio_sleep(timeout) {
+        return select! {
+            () = sleep.fuse() => report.fail(TestState::Dropped),
+            out = code().fuse() => out,
+        };
+    }
     select! {
         () = async {
             Delay::new(timeout).await;
-        }.fuse() => panic!("Timeout {:?} expired", timeout),
+        }.fuse() => report.fail(TestState::Dropped),
         out = code().fuse() => out,
     }
 }
 
+/// The tokio runtime's timer, if the test is running in a tokio runtime built with
+/// `enable_time()`: tokio panics when the timer is disabled, so that's caught and the
+/// panic message is not printed.
+#[cfg(feature = "tokio-timeout")]
+fn tokio_sleep(timeout: Duration) -> Option<tokio::time::Sleep> {
+    use std::{cell::Cell, sync::Once};
+
+    thread_local! {
+        static PROBING: Cell<bool> = const { Cell::new(false) };
+    }
+    static SILENCE_PROBE: Once = Once::new();
+
+    tokio::runtime::Handle::try_current().ok()?;
+    SILENCE_PROBE.call_once(|| {
+        let hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if !PROBING.with(Cell::get) {
+                hook(info)
+            }
+        }))
+    });
+    PROBING.with(|probing| probing.set(true));
+    let sleep = catch_unwind(|| tokio::time::sleep(timeout)).ok();
+    PROBING.with(|probing| probing.set(false));
+    sleep
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+
+    struct DropFlag(Arc<AtomicBool>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst)
+        }
+    }
     #[cfg(feature = "async-timeout")]
     mod async_version {
 
@@ -48,6 +427,27 @@ mod tests {
             assert_eq!(result, 4);
         }
 
+        async fn should_drop_the_timed_out_future() {
+            let dropped = Arc::new(AtomicBool::new(false));
+            let flag = dropped.clone();
+
+            let result = AssertUnwindSafe(execute_with_timeout_async(
+                move || {
+                    let flag = DropFlag(flag.clone());
+                    async move {
+                        let _flag = flag;
+                        futures::future::pending::<()>().await
+                    }
+                },
+                Duration::from_millis(10),
+            ))
+            .catch_unwind()
+            .await;
+
+            assert!(result.is_err());
+            assert!(dropped.load(Ordering::SeqCst));
+        }
+
         mod use_async_std_runtime {
             use super::*;
 
@@ -55,7 +455,7 @@ mod tests {
             #[should_panic]
             async fn should_fail() {
                 execute_with_timeout_async(
-                    || test(Duration::from_millis(20)),
+                    || test(Duration::from_secs(1)),
                     Duration::from_millis(10),
                 )
                 .await
@@ -64,11 +464,16 @@ mod tests {
             #[async_std::test]
             async fn should_pass() {
                 execute_with_timeout_async(
-                    || test(Duration::from_millis(10)),
-                    Duration::from_millis(20),
+                    || test(Duration::from_millis(1)),
+                    Duration::from_secs(1),
                 )
                 .await
             }
+
+            #[async_std::test]
+            async fn should_drop_the_future() {
+                should_drop_the_timed_out_future().await
+            }
         }
 
         mod use_tokio_runtime {
@@ -78,7 +483,7 @@ mod tests {
             #[should_panic]
             async fn should_fail() {
                 execute_with_timeout_async(
-                    || test(Duration::from_millis(20)),
+                    || test(Duration::from_secs(1)),
                     Duration::from_millis(10),
                 )
                 .await
@@ -87,14 +492,70 @@ mod tests {
             #[tokio::test]
             async fn should_pass() {
                 execute_with_timeout_async(
-                    || test(Duration::from_millis(10)),
-                    Duration::from_millis(20),
+                    || test(Duration::from_millis(1)),
+                    Duration::from_secs(1),
                 )
                 .await
             }
+
+            #[tokio::test]
+            async fn should_drop_the_future() {
+                should_drop_the_timed_out_future().await
+            }
+
+            fn runtime_without_timer() -> tokio::runtime::Runtime {
+                tokio::runtime::Builder::new_current_thread()
+                    .build()
+                    .unwrap()
+            }
+
+            #[test]
+            #[should_panic(expected = "expired")]
+            fn should_fail_also_if_the_runtime_has_no_timer() {
+                runtime_without_timer().block_on(execute_with_timeout_async(
+                    || test(Duration::from_secs(1)),
+                    Duration::from_millis(10),
+                ))
+            }
+
+            #[test]
+            fn should_pass_also_if_the_runtime_has_no_timer() {
+                runtime_without_timer().block_on(execute_with_timeout_async(
+                    || test(Duration::from_millis(1)),
+                    Duration::from_secs(1),
+                ))
+            }
+        }
+    }
+
+    #[test]
+    fn parse_duration_should_accept_units() {
+        for (value, expected) in [
+            ("30s", Some(Duration::from_secs(30))),
+            ("5", Some(Duration::from_secs(5))),
+            ("500ms", Some(Duration::from_millis(500))),
+            (" 2m ", Some(Duration::from_secs(120))),
+            ("1h", Some(Duration::from_secs(3600))),
+            ("10 s", Some(Duration::from_secs(10))),
+            ("10days", None),
+            ("s", None),
+            ("-3s", None),
+        ] {
+            assert_eq!(expected, parse_duration(value), "'{}'", value);
         }
     }
 
+    #[test]
+    fn parse_duration_should_reject_overflows() {
+        let max = u64::MAX.to_string();
+        assert_eq!(Some(Duration::from_secs(u64::MAX)), parse_duration(&max));
+        for unit in ["m", "h"] {
+            let value = format!("{}{}", max, unit);
+            assert_eq!(None, parse_duration(&value), "'{}'", value);
+        }
+        assert_eq!(None, parse_duration("18446744073709551616s"));
+    }
+
     mod thread_version {
         use super::*;
 
@@ -124,5 +585,183 @@ mod tests {
                 Duration::from_millis(30),
             )
         }
+
+        #[test]
+        #[should_panic(expected = "4 != 5")]
+        fn should_report_the_test_panic() {
+            execute_with_timeout_sync(
+                || assert!(delayed_sum(2, 2, Duration::ZERO) == 5, "4 != 5"),
+                Duration::from_millis(100),
+            )
+        }
+
+        #[test]
+        fn should_cancel_and_drop_the_test_values() {
+            let dropped = Arc::new(AtomicBool::new(false));
+            let flag = DropFlag(dropped.clone());
+            let flag = std::sync::Mutex::new(Some(flag));
+
+            let result = catch_unwind(|| {
+                execute_with_timeout_sync(
+                    move || {
+                        let _flag = flag.lock().unwrap().take();
+                        loop {
+                            sleep(Duration::from_secs(1));
+                        }
+                    },
+                    Duration::from_millis(30),
+                )
+            });
+
+            assert!(result.is_err());
+            assert!(dropped.load(Ordering::SeqCst));
+        }
+
+        #[test]
+        fn should_not_tear_down_the_fixtures_of_a_running_test() {
+            let torn_down = Arc::new(AtomicBool::new(false));
+            let flag = torn_down.clone();
+
+            let result = catch_unwind(AssertUnwindSafe(move || {
+                let _scope = crate::teardown::TearDownScope::new();
+                crate::teardown::register(&(), move |_| move || flag.store(true, Ordering::SeqCst));
+                execute_with_timeout_sync(
+                    || std::thread::sleep(Duration::from_millis(300)),
+                    Duration::from_millis(30),
+                )
+            }));
+
+            assert!(result.is_err());
+            assert!(!torn_down.load(Ordering::SeqCst));
+        }
+
+        #[test]
+        fn watchdog_should_pass() {
+            let result = execute_with_watchdog(
+                || {
+                    let rc = std::rc::Rc::new(2);
+                    assert_eq!(4, *rc + 2)
+                },
+                Duration::from_millis(100),
+            );
+
+            assert!(!result.is_failure());
+        }
+
+        #[test]
+        fn watchdog_should_report_the_returned_errors() {
+            let result =
+                execute_with_watchdog(|| Err::<(), _>("error"), Duration::from_millis(100));
+
+            assert!(result.is_failure());
+        }
+
+        #[test]
+        #[should_panic(expected = "Timeout")]
+        fn watchdog_should_cancel_the_test() {
+            execute_with_watchdog(
+                || loop {
+                    sleep(Duration::from_secs(1))
+                },
+                Duration::from_millis(30),
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "Timeout")]
+        fn watchdog_should_fail_slow_tests() {
+            execute_with_watchdog(
+                || std::thread::sleep(Duration::from_millis(50)),
+                Duration::from_millis(30),
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "still running")]
+        fn watchdog_should_fail_and_leak_a_test_that_never_stops() {
+            execute_with_watchdog(
+                || loop {
+                    std::thread::park()
+                },
+                Duration::from_millis(30),
+            );
+        }
+
+        #[test]
+        #[should_panic(expected = "4 != 5")]
+        fn watchdog_should_report_the_test_panic() {
+            execute_with_watchdog(
+                || assert!(delayed_sum(2, 2, Duration::ZERO) == 5, "4 != 5"),
+                Duration::from_millis(100),
+            );
+        }
+
+        fn timeout_report(execute: impl FnOnce()) -> String {
+            let _test = crate::context::TestContext::enter("module::case_3");
+            crate::context::TestContext::trace("delay", &Duration::from_secs(1));
+            let payload = catch_unwind(AssertUnwindSafe(execute)).unwrap_err();
+            payload.downcast::<String>().map(|s| *s).unwrap()
+        }
+
+        #[test]
+        fn should_report_test_arguments_and_where_it_stopped() {
+            let report = timeout_report(|| {
+                execute_with_timeout_sync(
+                    || loop {
+                        sleep(Duration::from_secs(1))
+                    },
+                    Duration::from_millis(30),
+                )
+            });
+
+            assert!(report.starts_with("Timeout 30ms expired\n"), "{}", report);
+            for expected in [
+                "test:     module::case_3",
+                "timeout:  30ms",
+                "elapsed:  ",
+                "state:    stopped at checkpoint()",
+                "delay = 1s",
+                "stack backtrace:",
+            ] {
+                assert!(
+                    report.contains(expected),
+                    "'{}' not in {}",
+                    expected,
+                    report
+                );
+            }
+        }
+
+        #[test]
+        fn should_report_a_test_that_never_reaches_a_checkpoint() {
+            let report = timeout_report(|| {
+                execute_with_timeout_sync(
+                    || std::thread::sleep(Duration::from_millis(300)),
+                    Duration::from_millis(30),
+                )
+            });
+
+            assert!(report.contains("still running"), "{}", report);
+            assert!(!report.contains("stack backtrace:"), "{}", report);
+        }
+
+        #[test]
+        fn watchdog_should_report_slow_tests() {
+            let report = timeout_report(|| {
+                execute_with_watchdog(
+                    || std::thread::sleep(Duration::from_millis(50)),
+                    Duration::from_millis(30),
+                );
+            });
+
+            assert!(report.contains("test:     module::case_3"), "{}", report);
+            assert!(report.contains("completed after the timeout"), "{}", report);
+        }
+
+        #[test]
+        fn checkpoint_should_do_nothing_outside_a_timeout() {
+            checkpoint();
+            assert!(!is_cancelled());
+        }
     }
 }
@@ -218,6 +218,158 @@ mod should {
         assert_eq!(1, occurences);
     }
 
+    #[test]
+    fn accept_once_attribute_on_async_fixture_and_call_it_just_once() {
+        let project = prj("once_async.rs").with_nocapture();
+        project.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+        project.add_dependency("tokio", r#"{version="*", features=["rt", "macros"]}"#);
+        project.add_dependency("actix-rt", r#""2.2.0""#);
+
+        let output = project.run_tests().unwrap();
+
+        // Just to see the errors if fixture doesn't compile
+        assert_in!(output.stderr.str(), "Exec fixture() just once");
+
+        let occurences = output.stderr.str().count("Exec fixture() just once");
+
+        assert_eq!(1, occurences);
+        assert!(output.status.success(), "{}", output.stdout.str());
+    }
+
+    #[test]
+    fn drop_once_drop_fixtures_just_once_at_exit() {
+        let project = prj("once_drop.rs").with_nocapture();
+        project.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+
+        let output = project.run_tests().unwrap();
+
+        for msg in [
+            "Drop sync once fixture",
+            "Drop async once fixture",
+            "Drop generic once fixture",
+        ] {
+            assert_eq!(1, output.stderr.str().count(msg), "{}", output.stderr.str());
+        }
+        // Still used by the timed out test's thread at exit
+        assert_not_in!(output.stderr.str(), "Drop stuck once fixture");
+        // Just the timed out test fails
+        assert_in!(output.stdout.str(), "6 passed; 1 failed");
+    }
+
+    #[test]
+    fn reset_once_reset_fixture_before_each_test() {
+        let project = prj("once_reset.rs").with_nocapture();
+        project.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+
+        let output = project.run_tests().unwrap();
+        let stderr = output.stderr.str();
+
+        assert_eq!(1, stderr.count("Exec store() just once"), "{}", stderr);
+        assert_eq!(1, stderr.count("Exec async_store() once"), "{}", stderr);
+        // 3 tests and the explicit hook call
+        assert_eq!(4, stderr.count("Reset counter"), "{}", stderr);
+        // Take the same fixtures in a different order without deadlock
+        assert_in!(output.stdout.str(), "test result: ok. 19 passed");
+        assert!(output.status.success(), "{}", output.stdout.str());
+    }
+
+    #[test]
+    fn release_once_reset_fixture_access_also_if_the_test_timed_out() {
+        let (output, _) = run_test("once_reset_timeout.rs");
+
+        TestResults::new().fail("stuck").ok("after").assert(output);
+    }
+
+    #[test]
+    fn share_module_scope_fixture_with_all_tests_of_the_same_function() {
+        let project = prj("scope.rs").with_nocapture();
+        project.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+
+        let output = project.run_tests().unwrap();
+        let stderr = output.stderr.str();
+        let stdout = output.stdout.str();
+        // Values should be dropped by the last test, not at exit
+        let tests_completed = stdout.find("test result:").unwrap();
+
+        for name in ["sync", "matrix", "async", "single", "ignored"] {
+            let load = format!("Load {} corpus", name);
+            let drop = format!("Drop {} corpus", name);
+            assert_eq!(1, stderr.count(&load), "{}", stderr);
+            assert_eq!(1, stdout.count(&drop), "{}", stdout);
+            assert!(stdout.find(&drop).unwrap() < tests_completed, "{}", stdout);
+        }
+        assert!(output.status.success(), "{}", output.stdout.str());
+    }
+
+    #[test]
+    fn accept_once_attribute_on_generic_fixture_and_call_it_once_for_each_type() {
+        let project = prj("once_generic.rs").with_nocapture();
+        project.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+
+        let output = project.run_tests().unwrap();
+
+        for msg in [
+            "Exec fixture() for in memory",
+            "Exec fixture() for on disk",
+            "Exec fixture() for impl",
+            "Exec fixture() for async in memory",
+            "Exec fixture() for async on disk",
+            "Exec fixture() for keyed 1",
+            "Exec fixture() for keyed false",
+            "Exec fixture() for described \"a\"",
+            "Exec fixture() for described 3",
+        ] {
+            // Just to see the errors if fixture doesn't compile
+            assert_in!(output.stderr.str(), msg);
+            assert_eq!(1, output.stderr.str().count(msg), "{}", msg);
+        }
+        assert!(output.status.success(), "{}", output.stdout.str());
+    }
+
+    #[test]
+    fn run_teardown_after_each_test_in_reverse_order() {
+        let prj = prj("teardown.rs").with_nocapture();
+        prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+
+        let output = prj.run_tests().unwrap();
+        let stderr = output.stderr.str().to_string();
+        let log = |test: &str| {
+            let prefix = format!("{}: ", test);
+            stderr
+                .lines()
+                .filter_map(|l| l.strip_prefix(&prefix))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            vec!["test(43)", "teardown child(43)", "teardown root"],
+            log("order")
+        );
+        assert_eq!(
+            vec![
+                "test(42, 43)",
+                "teardown child(43)",
+                "teardown root",
+                "teardown root"
+            ],
+            log("two_fixtures")
+        );
+        assert_eq!(vec!["test(11)", "teardown child(11)"], log("with"));
+        assert_eq!(
+            vec!["test(43)", "teardown child(43)", "teardown root"],
+            log("on_panic")
+        );
+        assert_eq!(
+            vec!["test(43, 1)", "teardown child(43)", "teardown root"],
+            log("cases::case_1")
+        );
+        assert_eq!(
+            vec!["test(43, 2)", "teardown child(43)", "teardown root"],
+            log("cases::case_2")
+        );
+        assert_eq!(vec!["test(42)", "teardown async"], log("async_test"));
+    }
+
     #[test]
     fn show_correct_errors() {
         let prj = prj("errors.rs");
@@ -299,7 +451,7 @@ mod should {
             output.stderr.str(),
             format!(
                 r#"
-                error: Cannot apply #[once] to async fixture.
+                error: Cannot apply #[teardown] to #[once] fixture.
                   --> {}/src/lib.rs:38:3
                    |
                 38 | #[once]
@@ -314,11 +466,11 @@ mod should {
             output.stderr.str(),
             format!(
                 r#"
-                error: Cannot apply #[once] on generic fixture.
-                  --> {}/src/lib.rs:43:3
+                error: Cannot apply #[teardown] to #[scope] fixture.
+                  --> {}/src/lib.rs:51:9
                    |
-                43 | #[once]
-                   |   ^^^^
+                51 | #[scope(module)]
+                   |         ^^^^^^
                 "#,
                 name
             )
@@ -329,11 +481,11 @@ mod should {
             output.stderr.str(),
             format!(
                 r#"
-                error: Cannot apply #[once] on generic fixture.
-                  --> {}/src/lib.rs:49:3
+                error[E0277]: `Cell<u32>` cannot be shared between threads safely
+                  --> {}/src/lib.rs:46:37
                    |
-                49 | #[once]
-                   |   ^^^^
+                46 | fn error_once_fixture_not_sync() -> std::cell::Cell<u32> {{
+                   |                                     ^^^ `Cell<u32>` cannot be shared between threads safely
                 "#,
                 name
             )
@@ -36,17 +36,20 @@ fn error_inject_a_fixture_more_than_once(f: String) {
 
 #[fixture]
 #[once]
-async fn error_async_once_fixture() {
+#[teardown(|_| || ())]
+fn error_teardown_once_fixture() -> u32 {
+    42
 }
 
 #[fixture]
 #[once]
-fn error_generics_once_fixture<T: std::fmt::Debug>() -> T {
-    42
+fn error_once_fixture_not_sync() -> std::cell::Cell<u32> {
+    std::cell::Cell::new(42)
 }
 
 #[fixture]
-#[once]
-fn error_generics_once_fixture() -> impl Iterator<Item: u32> {
-    std::iter::once(42)
+#[scope(module)]
+#[teardown(|_| || ())]
+fn error_teardown_scope_fixture() -> u32 {
+    42
 }
@@ -0,0 +1,34 @@
+use rstest::{fixture, rstest};
+
+#[fixture]
+#[once]
+async fn once_fixture() -> u32 {
+    eprintln!("Exec fixture() just once");
+    async_std::task::yield_now().await;
+    42
+}
+
+#[rstest]
+async fn base(#[future] once_fixture: &u32) {
+    assert_eq!(&42, once_fixture.await);
+}
+
+#[rstest]
+#[case(2)]
+#[case(3)]
+#[case(7)]
+async fn cases(#[future] once_fixture: &u32, #[case] divisor: u32) {
+    assert_eq!(0, *once_fixture.await % divisor);
+}
+
+#[rstest]
+#[tokio::test]
+async fn tokio(#[future] once_fixture: &u32) {
+    assert_eq!(&42, once_fixture.await);
+}
+
+#[rstest]
+#[actix_rt::test]
+async fn actix(#[future] once_fixture: &u32) {
+    assert_eq!(&42, once_fixture.await);
+}
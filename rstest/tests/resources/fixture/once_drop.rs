@@ -0,0 +1,64 @@
+use rstest::{fixture, rstest, scope::Shared};
+use std::time::Duration;
+
+struct Resource(&'static str);
+
+impl Drop for Resource {
+    fn drop(&mut self) {
+        eprintln!("Drop {} once fixture", self.0);
+    }
+}
+
+#[fixture]
+#[once(drop)]
+fn sync_resource() -> Resource {
+    Resource("sync")
+}
+
+#[fixture]
+#[once(drop)]
+async fn async_resource() -> Resource {
+    Resource("async")
+}
+
+#[fixture]
+#[once(drop)]
+fn generic_resource<T: Default>() -> (T, Resource) {
+    (T::default(), Resource("generic"))
+}
+
+#[rstest]
+#[case(1)]
+#[case(2)]
+fn sync_cases(sync_resource: Shared<Resource>, #[case] _case: u32) {
+    assert_eq!("sync", sync_resource.0);
+}
+
+#[rstest]
+#[case(1)]
+#[case(2)]
+async fn async_cases(#[future] async_resource: Shared<Resource>, #[case] _case: u32) {
+    assert_eq!("async", async_resource.await.0);
+}
+
+#[rstest]
+#[case(1)]
+#[case(2)]
+fn generic_cases(generic_resource: Shared<(u32, Resource)>, #[case] _case: u32) {
+    assert_eq!("generic", generic_resource.1 .0);
+}
+
+#[fixture]
+#[once(drop)]
+fn stuck_resource() -> Resource {
+    Resource("stuck")
+}
+
+#[rstest]
+#[timeout(Duration::from_millis(50))]
+fn stuck(stuck_resource: Shared<Resource>) {
+    loop {
+        assert_eq!("stuck", stuck_resource.0);
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
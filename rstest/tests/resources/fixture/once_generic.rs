@@ -0,0 +1,120 @@
+use rstest::{fixture, rstest};
+
+trait Repository {
+    fn name(&self) -> &'static str;
+}
+
+#[derive(Default)]
+struct InMemory;
+
+impl Repository for InMemory {
+    fn name(&self) -> &'static str {
+        "in memory"
+    }
+}
+
+#[derive(Default)]
+struct OnDisk;
+
+impl Repository for OnDisk {
+    fn name(&self) -> &'static str {
+        "on disk"
+    }
+}
+
+#[fixture]
+#[once]
+fn repo<R: Repository + Default>() -> R {
+    let repo = R::default();
+    eprintln!("Exec fixture() for {}", repo.name());
+    repo
+}
+
+#[rstest]
+fn in_memory(repo: &InMemory) {
+    assert_eq!("in memory", repo.name());
+}
+
+#[rstest]
+#[case(1)]
+#[case(2)]
+fn in_memory_cases(repo: &InMemory, #[case] _n: u32) {
+    assert_eq!("in memory", repo.name());
+}
+
+#[rstest]
+fn on_disk(repo: &OnDisk) {
+    assert_eq!("on disk", repo.name());
+}
+
+#[rstest]
+#[case(1)]
+#[case(2)]
+fn on_disk_cases(repo: &OnDisk, #[case] _n: u32) {
+    assert_eq!("on disk", repo.name());
+}
+
+#[fixture]
+#[once]
+fn text() -> impl AsRef<str> {
+    eprintln!("Exec fixture() for impl");
+    String::from("42")
+}
+
+#[rstest]
+fn impl_return(text: &impl AsRef<str>) {
+    assert_eq!("42", text.as_ref());
+}
+
+#[rstest]
+#[case(1)]
+#[case(2)]
+fn impl_return_cases(text: &impl AsRef<str>, #[case] _n: u32) {
+    assert_eq!("42", text.as_ref());
+}
+
+#[fixture]
+#[once]
+async fn async_repo<R: Repository + Default>() -> R {
+    let repo = R::default();
+    eprintln!("Exec fixture() for async {}", repo.name());
+    repo
+}
+
+#[rstest]
+async fn async_in_memory(#[future] async_repo: &InMemory) {
+    assert_eq!("in memory", async_repo.await.name());
+}
+
+#[rstest]
+async fn async_on_disk(#[future] async_repo: &OnDisk) {
+    assert_eq!("on disk", async_repo.await.name());
+}
+
+#[fixture]
+#[once]
+fn keyed<T: std::fmt::Debug>(#[default(0_u32)] v: T) -> String {
+    eprintln!("Exec fixture() for keyed {:?}", v);
+    format!("{:?}", v)
+}
+
+#[rstest]
+fn generic_parameter_not_in_return_type() {
+    assert_eq!("1", keyed::get(1_u32));
+    assert_eq!("false", keyed::get(false));
+    assert_eq!("1", keyed::get(2_u32));
+}
+
+#[fixture]
+#[once]
+fn described(#[default(0)] v: impl std::fmt::Debug) -> String {
+    eprintln!("Exec fixture() for described {:?}", v);
+    format!("{:?}", v)
+}
+
+#[rstest]
+fn impl_argument_not_in_return_type() {
+    assert_eq!("\"a\"", described::get("a"));
+    assert_eq!("3", described::get(3));
+    assert_eq!("\"a\"", described::get("b"));
+}
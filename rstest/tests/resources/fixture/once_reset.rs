@@ -0,0 +1,117 @@
+use rstest::{fixture, rstest, Reset};
+use std::sync::{
+    atomic::{AtomicU32, Ordering},
+    Mutex,
+};
+
+#[fixture]
+#[once(reset)]
+fn store() -> Mutex<Vec<u32>> {
+    eprintln!("Exec store() just once");
+    Default::default()
+}
+
+#[rstest]
+#[case(1)]
+#[case(2)]
+#[case(3)]
+#[case(4)]
+fn cases(store: &Mutex<Vec<u32>>, #[case] value: u32) {
+    store.lock().unwrap().push(value);
+    std::thread::sleep(std::time::Duration::from_millis(10));
+    assert_eq!(vec![value], *store.lock().unwrap());
+}
+
+struct Counter(AtomicU32);
+
+impl Reset for Counter {
+    fn reset(&self) {
+        eprintln!("Reset counter");
+        self.0.store(0, Ordering::SeqCst)
+    }
+}
+
+#[fixture]
+#[once(reset)]
+fn counter() -> Counter {
+    Counter(AtomicU32::new(0))
+}
+
+#[rstest]
+fn first(counter: &Counter) {
+    assert_eq!(0, counter.0.fetch_add(1, Ordering::SeqCst));
+}
+
+#[rstest]
+fn second(counter: &Counter) {
+    assert_eq!(0, counter.0.fetch_add(1, Ordering::SeqCst));
+}
+
+#[rstest]
+fn call_reset_hook(counter: &Counter) {
+    counter.0.store(42, Ordering::SeqCst);
+    counter::reset(counter);
+    assert_eq!(0, counter.0.load(Ordering::SeqCst));
+}
+
+#[fixture]
+#[once(reset)]
+async fn async_store() -> Mutex<Vec<u32>> {
+    eprintln!("Exec async_store() once");
+    Default::default()
+}
+
+#[rstest]
+#[case(1)]
+#[case(2)]
+#[case(3)]
+#[case(4)]
+async fn async_cases(#[future] async_store: &Mutex<Vec<u32>>, #[case] value: u32) {
+    let store = async_store.await;
+    store.lock().unwrap().push(value);
+    async_std::task::sleep(std::time::Duration::from_millis(10)).await;
+    assert_eq!(vec![value], *store.lock().unwrap());
+}
+
+#[fixture]
+#[once(reset)]
+fn first_store() -> Mutex<Vec<u32>> {
+    Default::default()
+}
+
+#[fixture]
+#[once(reset)]
+fn second_store() -> Mutex<Vec<u32>> {
+    Default::default()
+}
+
+#[fixture]
+fn pause() {
+    std::thread::sleep(std::time::Duration::from_millis(10));
+}
+
+#[rstest]
+fn first_then_second(
+    #[values(1, 2, 3, 4)] n: u32,
+    first_store: &Mutex<Vec<u32>>,
+    #[from(pause)] _pause: (),
+    second_store: &Mutex<Vec<u32>>,
+) {
+    first_store.lock().unwrap().push(n);
+    second_store.lock().unwrap().push(n);
+    assert_eq!(vec![n], *first_store.lock().unwrap());
+    assert_eq!(vec![n], *second_store.lock().unwrap());
+}
+
+#[rstest]
+fn second_then_first(
+    #[values(1, 2, 3, 4)] n: u32,
+    second_store: &Mutex<Vec<u32>>,
+    #[from(pause)] _pause: (),
+    first_store: &Mutex<Vec<u32>>,
+) {
+    first_store.lock().unwrap().push(n);
+    second_store.lock().unwrap().push(n);
+    assert_eq!(vec![n], *first_store.lock().unwrap());
+    assert_eq!(vec![n], *second_store.lock().unwrap());
+}
@@ -0,0 +1,37 @@
+use rstest::{fixture, rstest};
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Mutex,
+};
+use std::time::{Duration, Instant};
+
+static STUCK: AtomicBool = AtomicBool::new(false);
+
+#[fixture]
+#[once(reset)]
+fn store() -> Mutex<Vec<u32>> {
+    Default::default()
+}
+
+#[fixture]
+fn wait_stuck() {
+    let start = Instant::now();
+    while !STUCK.load(Ordering::SeqCst) && start.elapsed() < Duration::from_secs(5) {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[rstest]
+#[timeout(Duration::from_millis(50))]
+fn stuck(store: &Mutex<Vec<u32>>) {
+    store.lock().unwrap().push(1);
+    STUCK.store(true, Ordering::SeqCst);
+    loop {
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[rstest]
+fn after(#[from(wait_stuck)] _wait: (), store: &Mutex<Vec<u32>>) {
+    assert!(store.lock().unwrap().is_empty());
+}
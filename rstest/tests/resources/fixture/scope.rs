@@ -0,0 +1,60 @@
+use rstest::{fixture, rstest, scope::Shared};
+
+struct Corpus(&'static str);
+
+impl Drop for Corpus {
+    fn drop(&mut self) {
+        println!("Drop {} corpus", self.0);
+    }
+}
+
+#[fixture]
+#[scope(module)]
+fn corpus(#[default("sync")] name: &'static str) -> Corpus {
+    eprintln!("Load {} corpus", name);
+    Corpus(name)
+}
+
+#[fixture]
+#[scope(module)]
+async fn async_corpus() -> Corpus {
+    eprintln!("Load async corpus");
+    Corpus("async")
+}
+
+#[rstest]
+#[case(1)]
+#[case(2)]
+#[case(3)]
+fn cases(corpus: Shared<Corpus>, #[case] _n: u32) {
+    assert_eq!("sync", corpus.0);
+}
+
+#[rstest]
+fn matrix(
+    #[with("matrix")] corpus: Shared<Corpus>,
+    #[values(1, 2)] _a: u32,
+    #[values(3, 4)] _b: u32,
+) {
+    assert_eq!("matrix", corpus.0);
+}
+
+#[rstest]
+#[case(1)]
+#[case(2)]
+async fn async_cases(#[future] async_corpus: Shared<Corpus>, #[case] _n: u32) {
+    assert_eq!("async", async_corpus.await.0);
+}
+
+#[rstest]
+fn single(#[with("single")] corpus: Shared<Corpus>) {
+    assert_eq!("single", corpus.0);
+}
+
+#[rstest]
+#[case(1)]
+#[ignore]
+#[case(2)]
+fn ignored_case(#[with("ignored")] corpus: Shared<Corpus>, #[case] _n: u32) {
+    assert_eq!("ignored", corpus.0);
+}
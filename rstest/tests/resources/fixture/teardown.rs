@@ -0,0 +1,61 @@
+use rstest::*;
+
+fn log(msg: &str) {
+    eprintln!("{}: {}", std::thread::current().name().unwrap(), msg);
+}
+
+#[fixture]
+#[teardown(|_| || log("teardown root"))]
+fn root() -> u32 {
+    42
+}
+
+#[fixture]
+#[teardown(|v| {
+    let v = *v;
+    move || log(&format!("teardown child({})", v))
+})]
+fn child(root: u32) -> u32 {
+    root + 1
+}
+
+#[fixture]
+#[teardown(|_| || log("teardown async"))]
+async fn async_fixture() -> u32 {
+    42
+}
+
+#[rstest]
+fn order(child: u32) {
+    log(&format!("test({})", child));
+}
+
+#[rstest]
+fn two_fixtures(root: u32, child: u32) {
+    log(&format!("test({}, {})", root, child));
+}
+
+#[rstest]
+fn with(#[with(10)] child: u32) {
+    log(&format!("test({})", child));
+}
+
+#[rstest]
+#[should_panic]
+fn on_panic(child: u32) {
+    log(&format!("test({})", child));
+    panic!("Expected")
+}
+
+#[rstest]
+#[case(1)]
+#[case(2)]
+fn cases(child: u32, #[case] n: u32) {
+    log(&format!("test({}, {})", child, n));
+}
+
+#[rstest]
+#[async_std::test]
+async fn async_test(#[future] async_fixture: u32) {
+    log(&format!("test({})", async_fixture.await));
+}
@@ -0,0 +1,28 @@
+use rstest::*;
+
+#[rstest(auto_names)]
+#[case(0, 0)]
+#[case(1, 1)]
+#[case::forty_two(42, 42)]
+#[case("Hello World".len(), 11)]
+fn cases(#[case] input: usize, #[case] expected: usize) {
+    assert_eq!(expected, input)
+}
+
+#[rstest(::auto_names)]
+fn values(
+    #[values("127.0.0.1:8000", "10.0.0.1:8000", "[::1]:8000")] addr: std::net::SocketAddr,
+    #[values(first => 1, 2)] n: u32,
+) {
+    assert_eq!(8000, addr.port() * n as u16 / n as u16)
+}
+
+#[rstest]
+fn not_enabled(#[values("a", "b")] name: &str) {
+    assert!(!name.is_empty())
+}
+
+#[rstest(auto_names, case(1), case(2))]
+fn argument_named_auto_names(auto_names: u32) {
+    assert!(auto_names > 0)
+}
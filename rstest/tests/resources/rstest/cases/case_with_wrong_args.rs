@@ -8,3 +8,7 @@ fn error_less_arguments(a: u32, b: u32) {}
 #[rstest(a, case(42, 43), case(12), case(24, 34))]
 fn error_too_much_arguments(a: u32) {}
 
+#[cfg(test)]
+#[rstest]
+#[case("Not a description", 42)]
+fn error_leading_string(#[case] a: u32) {}
@@ -0,0 +1,21 @@
+use rstest::rstest;
+use std::net::SocketAddr;
+
+#[rstest]
+#[table("vectors/sum.csv")]
+fn csv(#[case] a: u32, #[case] b: u32, #[case] expected: u32) {
+    assert_eq!(expected, a + b);
+}
+
+#[rstest]
+#[case(1, 1, 2)]
+#[table("vectors/sum.json")]
+fn json(#[case] a: i32, #[case] b: i32, #[case] expected: i32) {
+    assert_eq!(expected, a + b);
+}
+
+#[rstest]
+#[table("vectors/addr.toml")]
+fn toml(#[case] addr: SocketAddr, #[case] port: u16, #[values(1, 2)] _n: u32) {
+    assert_eq!(port, addr.port());
+}
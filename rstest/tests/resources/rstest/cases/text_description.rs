@@ -0,0 +1,27 @@
+use rstest::rstest;
+
+#[rstest]
+#[case(desc = "http 1.1 keep-alive", true)]
+#[case::ident_wins(desc = "Shown in banner", true)]
+#[case(desc = "with {braces} & fails!", false)]
+#[case(true)]
+fn attr(#[case] expected: bool) {
+    assert!(expected);
+}
+
+#[rstest(expected, case(desc = "Old syntax", true))]
+fn list(expected: bool) {
+    assert!(expected);
+}
+
+#[rstest]
+#[case("Just a string", "Just a string")]
+fn same_arity(#[case] a: &str, #[case] b: &str) {
+    assert_eq!(a, b);
+}
+
+#[rstest]
+#[case(desc = "In matrix", 1)]
+fn matrix(#[case] a: u32, #[values(1, 2)] b: u32) {
+    assert!(a <= b);
+}
@@ -0,0 +1,64 @@
+use rstest::*;
+use std::{rc::Rc, time::Duration};
+
+fn ms(ms: u64) -> Duration {
+    Duration::from_millis(ms)
+}
+
+#[fixture]
+fn not_send() -> Rc<u32> {
+    Rc::new(42)
+}
+
+#[rstest]
+fn pass(not_send: Rc<u32>) {
+    std::thread::sleep(ms(10));
+    assert_eq!(42, *not_send);
+}
+
+#[rstest]
+fn fail_cancelled() {
+    rstest::timeout::sleep(Duration::from_secs(60));
+}
+
+#[rstest]
+#[no_timeout]
+fn opt_out() {
+    std::thread::sleep(ms(400));
+}
+
+#[rstest]
+#[timeout(ms(2000))]
+fn explicit_timeout() {
+    std::thread::sleep(ms(400));
+}
+
+#[rstest]
+#[case::pass(10)]
+#[no_timeout]
+#[case::opt_out(400)]
+#[case::fail(400)]
+fn cases(#[case] delay: u64) {
+    std::thread::sleep(ms(delay));
+}
+
+#[rstest]
+async fn async_fail() {
+    async_std::task::sleep(Duration::from_secs(60)).await;
+}
+
+#[rstest]
+fn fail_never_stops(not_send: Rc<u32>) {
+    loop {
+        assert_eq!(42, *not_send);
+        std::thread::sleep(ms(10));
+    }
+}
+
+#[rstest]
+fn pass_not_send_result(not_send: Rc<u32>) -> Result<(), Rc<u32>> {
+    match *not_send {
+        42 => Ok(()),
+        _ => Err(not_send),
+    }
+}
@@ -0,0 +1,39 @@
+use rstest::*;
+use std::path::PathBuf;
+
+fn check_content(path: PathBuf) {
+    let name = path.file_name().unwrap().to_str().unwrap().to_owned();
+    let content = std::fs::read_to_string(&path).unwrap();
+
+    assert_eq!(name, content);
+}
+
+#[rstest]
+fn start_with_name(#[files("files/**/*.txt")] path: PathBuf) {
+    check_content(path)
+}
+
+#[rstest]
+fn exclude(#[files("files/**/*.txt", exclude = "**/not_*")] path: PathBuf) {
+    check_content(path)
+}
+
+#[rstest]
+fn include_dot_files(#[files("files/**/*.txt", include_dot_files)] path: PathBuf) {
+    check_content(path)
+}
+
+#[rstest]
+fn more_globs(#[files("files/*_0.txt", "files/sub/*.txt")] path: PathBuf, #[values(1, 2)] _n: u32) {
+    check_content(path)
+}
+
+#[rstest]
+fn embed_str(#[files("files/*.txt", mode = "str")] content: &str) {
+    assert!(content.starts_with("element_"))
+}
+
+#[rstest]
+fn embed_bytes(#[files("files/*.txt", mode = "bytes")] content: &[u8]) {
+    assert!(content.starts_with(b"element_"))
+}
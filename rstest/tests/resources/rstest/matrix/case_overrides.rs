@@ -0,0 +1,27 @@
+use rstest::rstest;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    A,
+    B,
+}
+
+#[rstest]
+#[case(1)]
+#[case(2)]
+#[case(3, mode = [Mode::A])]
+#[case::replaced(4, mode = [Mode::B], n = [40, 41])]
+fn overrides(#[case] case: u32, #[values(Mode::A, Mode::B)] mode: Mode, #[values(1, 2)] n: u32) {
+    assert!(case != 3 || mode == Mode::A);
+    assert!(case != 4 || (mode == Mode::B && n >= 40));
+    assert!(case == 4 || n < 40);
+}
+
+#[rstest(value,
+    case(1),
+    case(3, mode = [Mode::A]),
+    mode => [Mode::A, Mode::B]
+)]
+fn list(value: u32, mode: Mode) {
+    assert!(value != 3 || mode == Mode::A);
+}
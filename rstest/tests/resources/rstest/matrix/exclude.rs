@@ -0,0 +1,30 @@
+use rstest::rstest;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Mode {
+    A,
+    B,
+}
+
+#[rstest]
+#[exclude(mode = Mode::B, n = 2)]
+fn exclude(#[values(Mode::A, Mode::B)] mode: Mode, #[values(1, 2)] n: u32) {
+    assert!(mode != Mode::B || n != 2);
+}
+
+#[rstest]
+#[exclude(n = zero)]
+#[exclude(mode = Mode::A, n = 1)]
+fn exclude_labels(#[values(Mode::A, Mode::B)] mode: Mode, #[values(zero => 0, 1)] n: u32) {
+    assert!(n != 0);
+    assert!(mode != Mode::A || n != 1);
+}
+
+#[rstest]
+#[case(1)]
+#[case(2)]
+#[exclude(mode = Mode::A)]
+fn exclude_cases(#[case] c: u32, #[values(Mode::A, Mode::B)] mode: Mode) {
+    assert!(c > 0);
+    assert_eq!(Mode::B, mode);
+}
@@ -0,0 +1,17 @@
+use rstest::rstest;
+
+#[rstest]
+fn attr(
+    #[values(ascii => "abc", unicode => "ñ", #[name(empty)] "")] name: &str,
+    #[values(min => 0, 42)] age: u8,
+) {
+    assert!(name.len() < 3 || age > 0);
+}
+
+#[rstest(
+    name => [ascii => "abc", #[name(empty)] ""],
+    age => [min => 0, max => 255]
+)]
+fn list(name: &str, age: u8) {
+    assert!(name.len() < 3 || age > 0);
+}
@@ -0,0 +1,27 @@
+use rstest::rstest;
+
+#[rstest]
+#[matrix(zip)]
+fn zip(#[values(1, 2, 3)] a: u32, #[values(2, 4, 6)] b: u32) {
+    assert_eq!(2 * a, b);
+}
+
+#[rstest]
+#[matrix(zip)]
+#[case(1)]
+#[case(2)]
+fn zip_cases(#[case] k: u32, #[values(1, 2)] a: u32, #[values(10, 20)] b: u32) {
+    assert_eq!(10 * a, b);
+    assert!(k > 0);
+}
+
+#[rstest(a => [1, 2], b => [10, 20] ::matrix(zip))]
+fn zip_list(a: u32, b: u32) {
+    assert_eq!(10 * a, b);
+}
+
+#[rstest]
+#[matrix(pairwise)]
+fn pairwise(#[values(1, 2)] a: u32, #[values(1, 2)] b: u32, #[values(1, 2)] c: u32) {
+    assert_eq!(1, (a + b + c) % 2);
+}
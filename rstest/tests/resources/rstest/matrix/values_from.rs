@@ -0,0 +1,32 @@
+use rstest::rstest;
+
+const CORNER_CASES: [i32; 3] = [i32::MIN, 0, i32::MAX];
+
+mod shared {
+    pub const NAMES: &[&str] = &["", "a", "long name"];
+}
+
+#[rstest]
+fn const_array(#[values_from(CORNER_CASES, len = 3)] n: i32) {
+    assert!(n == 0 || n.abs_diff(0) > 1000);
+}
+
+#[rstest]
+fn const_slice(
+    #[values_from(crate::shared::NAMES, len = 3)] name: &str,
+    #[values(1, 2)] times: usize,
+) {
+    assert!(name.repeat(times).len() < 10);
+}
+
+#[derive(Clone, Debug)]
+struct Range {
+    bounds: std::ops::Range<u32>,
+}
+
+const RANGES: [Range; 2] = [Range { bounds: 0..3 }, Range { bounds: 5..5 }];
+
+#[rstest]
+fn not_copy(#[values_from(RANGES, len = 2)] range: Range) {
+    assert!(!range.bounds.is_empty(), "{:?}", range);
+}
@@ -0,0 +1,67 @@
+use rstest::*;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+fn next(counter: &AtomicU32) -> u32 {
+    counter.fetch_add(1, Ordering::SeqCst) + 1
+}
+
+static FLAKY: AtomicU32 = AtomicU32::new(0);
+
+#[rstest]
+#[retries(2)]
+fn flaky() {
+    assert!(next(&FLAKY) > 2);
+}
+
+static ALWAYS: AtomicU32 = AtomicU32::new(0);
+
+#[rstest]
+#[retries(2)]
+fn always_fail() {
+    panic!("attempt {} failed", next(&ALWAYS));
+}
+
+static RESOLVED: AtomicU32 = AtomicU32::new(0);
+
+#[fixture]
+fn attempt() -> u32 {
+    next(&RESOLVED)
+}
+
+#[rstest]
+#[retries(3)]
+fn resolve_fixtures_every_attempt(attempt: u32) {
+    assert_eq!(3, attempt);
+}
+
+static RESULT: AtomicU32 = AtomicU32::new(0);
+
+#[rstest]
+#[retries(1)]
+fn retry_errors() -> Result<(), String> {
+    match next(&RESULT) {
+        1 => Err("first attempt".to_string()),
+        _ => Ok(()),
+    }
+}
+
+static CASE: AtomicU32 = AtomicU32::new(0);
+static CASE_NO_RETRIES: AtomicU32 = AtomicU32::new(0);
+
+#[rstest]
+#[retries(1)]
+#[case::retried(&CASE)]
+#[case::no_retries(&CASE_NO_RETRIES)]
+fn cases(#[case] counter: &AtomicU32) {
+    assert!(next(counter) > 1);
+}
+
+static ASYNC: AtomicU32 = AtomicU32::new(0);
+
+#[rstest]
+#[retries(2)]
+#[async_std::test]
+async fn async_flaky() {
+    async_std::task::yield_now().await;
+    assert!(next(&ASYNC) > 2);
+}
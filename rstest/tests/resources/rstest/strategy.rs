@@ -0,0 +1,49 @@
+use rstest::strategy::{any, vec};
+use rstest::*;
+
+#[fixture]
+fn offset() -> u32 {
+    1000
+}
+
+#[rstest]
+fn inject_fixtures(offset: u32, #[strategy(0..1000u32)] a: u32) {
+    assert!(a + offset < 2000);
+}
+
+#[rstest]
+fn fail_and_shrink(#[strategy(0..1000u32)] a: u32) {
+    assert!(a < 500);
+}
+
+#[rstest]
+fn more_strategies(#[strategy(any::<u8>())] a: u8, #[strategy(any::<bool>())] b: bool) {
+    assert!(a as u32 + b as u32 <= 256);
+}
+
+#[rstest]
+fn sorted(#[strategy(vec(any::<i32>(), 0..20))] mut v: Vec<i32>) {
+    v.sort();
+    assert!(v.windows(2).all(|w| w[0] <= w[1]));
+}
+
+#[rstest]
+fn return_result(#[strategy(0..100u8)] a: u8) -> Result<(), String> {
+    if a < 50 {
+        Ok(())
+    } else {
+        Err(format!("{} is too big", a))
+    }
+}
+
+#[rstest]
+#[case::one(1)]
+#[case::zero(0)]
+fn cases(#[case] divisor: u32, #[strategy(1..100u32)] a: u32) {
+    assert!(a / divisor <= a);
+}
+
+#[rstest]
+fn matrix(#[values(1, 3)] m: u32, #[strategy(0..100u32)] a: u32) {
+    assert!(a * m < 200);
+}
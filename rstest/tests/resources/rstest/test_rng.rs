@@ -0,0 +1,35 @@
+use rstest::*;
+
+#[fixture]
+fn shuffled(mut test_rng: TestRng) -> Vec<u32> {
+    let mut items = (0..10).collect::<Vec<u32>>();
+    for i in (1..items.len()).rev() {
+        items.swap(i, test_rng.gen(0..=i));
+    }
+    items
+}
+
+#[rstest]
+fn seeded_by_test_name(test_rng: TestRng) {
+    let name = rstest::context::current_test().unwrap();
+
+    assert!(name.ends_with("::seeded_by_test_name"));
+    assert_eq!(TestRng::for_test(name).seed(), test_rng.seed());
+}
+
+#[rstest]
+fn fixtures_use_the_same_seed(shuffled: Vec<u32>, mut test_rng: TestRng) {
+    let mut expected = (0..10).collect::<Vec<u32>>();
+    for i in (1..expected.len()).rev() {
+        expected.swap(i, test_rng.gen(0..=i));
+    }
+
+    assert_eq!(expected, shuffled);
+}
+
+#[rstest]
+#[case::one(1)]
+#[case::two(2)]
+fn fail(#[case] n: u32, mut test_rng: TestRng) {
+    assert!(test_rng.gen(0..10u32) > 100 * n);
+}
@@ -0,0 +1,39 @@
+use rstest::timeout::{checkpoint, sleep};
+use rstest::*;
+use std::time::Duration;
+
+struct Resource;
+
+impl Drop for Resource {
+    fn drop(&mut self) {
+        println!("Resource released");
+    }
+}
+
+#[fixture]
+fn resource() -> Resource {
+    Resource
+}
+
+#[rstest]
+#[timeout(Duration::from_millis(50))]
+fn sleep_forever(_resource: Resource) {
+    loop {
+        sleep(Duration::from_secs(1));
+    }
+}
+
+#[rstest]
+#[timeout(Duration::from_millis(50))]
+fn busy_loop(_resource: Resource) {
+    loop {
+        checkpoint();
+        std::hint::spin_loop();
+    }
+}
+
+#[rstest]
+#[timeout(Duration::from_millis(500))]
+fn pass(_resource: Resource) {
+    sleep(Duration::from_millis(10));
+}
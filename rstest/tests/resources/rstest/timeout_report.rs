@@ -0,0 +1,20 @@
+use rstest::timeout::sleep;
+use rstest::*;
+use std::time::Duration;
+
+#[rstest]
+#[case::fast(Duration::from_millis(1))]
+#[case::stuck(Duration::from_secs(60))]
+#[trace]
+#[timeout(Duration::from_millis(50))]
+fn sync_test(#[case] delay: Duration) {
+    sleep(delay);
+}
+
+#[rstest]
+#[trace]
+#[timeout(Duration::from_millis(50))]
+#[async_std::test]
+async fn async_test(#[values(1000)] delay_ms: u64) {
+    async_std::task::sleep(Duration::from_millis(delay_ms)).await;
+}
@@ -0,0 +1,16 @@
+use rstest::*;
+use std::time::Duration;
+
+#[rstest]
+#[tokio::test(start_paused = true)]
+#[timeout(Duration::from_secs(60))]
+async fn paused_clock_pass() {
+    tokio::time::sleep(Duration::from_secs(30)).await;
+}
+
+#[rstest]
+#[tokio::test(start_paused = true)]
+#[timeout(Duration::from_secs(10))]
+async fn paused_clock_fail() {
+    tokio::time::sleep(Duration::from_secs(30)).await;
+}
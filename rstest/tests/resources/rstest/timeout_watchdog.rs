@@ -0,0 +1,65 @@
+use rstest::timeout::sleep;
+use rstest::*;
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::time::Duration;
+
+#[fixture]
+fn shared() -> Rc<RefCell<Vec<u32>>> {
+    Rc::new(RefCell::new(vec![1, 2, 3]))
+}
+
+#[rstest]
+#[timeout(Duration::from_millis(100), mode = watchdog)]
+fn pass(shared: Rc<RefCell<Vec<u32>>>) {
+    shared.borrow_mut().push(4);
+    assert_eq!(4, shared.borrow().len());
+}
+
+#[rstest]
+#[timeout(Duration::from_millis(50), mode = watchdog)]
+fn fail_timeout(shared: Rc<RefCell<Vec<u32>>>) {
+    loop {
+        shared.borrow_mut().push(0);
+        sleep(Duration::from_millis(10));
+    }
+}
+
+#[rstest]
+#[timeout(Duration::from_millis(100), mode = watchdog)]
+fn fail_value(shared: Rc<RefCell<Vec<u32>>>) {
+    assert_eq!(42, shared.borrow().len());
+}
+
+#[rstest]
+#[case::pass(Duration::from_millis(1))]
+#[case::fail(Duration::from_secs(60))]
+#[timeout(Duration::from_millis(50), mode = watchdog)]
+fn cases(shared: Rc<RefCell<Vec<u32>>>, #[case] delay: Duration) {
+    sleep(delay);
+    assert_eq!(3, shared.borrow().len());
+}
+
+#[rstest]
+#[timeout(Duration::from_millis(50), mode = watchdog)]
+fn fail_never_stops(shared: Rc<RefCell<Vec<u32>>>) {
+    loop {
+        shared.borrow_mut().push(0);
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+#[rstest]
+#[timeout(Duration::from_millis(100), mode = watchdog)]
+fn pass_result(shared: Rc<RefCell<Vec<u32>>>) -> Result<(), Rc<RefCell<Vec<u32>>>> {
+    match shared.borrow().len() {
+        3 => Ok(()),
+        _ => Err(shared.clone()),
+    }
+}
+
+#[rstest]
+#[timeout(Duration::from_millis(100), mode = watchdog)]
+fn fail_result(shared: Rc<RefCell<Vec<u32>>>) -> Result<(), Rc<RefCell<Vec<u32>>>> {
+    Err(shared)
+}
@@ -405,6 +405,65 @@ mod cases {
             .assert(output);
     }
 
+    #[test]
+    fn cases_from_table_files() {
+        let prj = crate::base_prj().set_code_file(resources(res("table.rs")));
+        prj.add_dependency(
+            "rstest",
+            &format!(
+                r#"{{path="{}", features=["table-json", "table-toml"]}}"#,
+                prj.exec_dir_str()
+            ),
+        );
+        let files = [
+            ("sum.csv", "a,b,expected\n1,2,3\n2,2,5\n"),
+            (
+                "sum.json",
+                r#"[{"name": "negative", "a": -1, "b": -2, "expected": -3}, {"a": 0, "b": 0, "expected": 0}]"#,
+            ),
+            (
+                "addr.toml",
+                "[[case]]\nname = \"local\"\naddr = \"127.0.0.1:8080\"\nport = 8080\n",
+            ),
+        ];
+        for (name, content) in files {
+            let path = prj.path().join("vectors").join(name);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+
+        let output = prj.run_tests().unwrap();
+
+        TestResults::new()
+            .ok("csv::case_1_row_1")
+            .fail("csv::case_2_row_2")
+            .ok("json::case_1")
+            .ok("json::case_2_negative")
+            .ok("json::case_3_row_2")
+            .ok("toml::case_1_local::_n_1")
+            .ok("toml::case_1_local::_n_2")
+            .assert(output);
+    }
+
+    #[test]
+    fn case_text_description() {
+        let (output, _) = run_test(res("text_description.rs"));
+        let out = output.stdout.str().to_string();
+
+        TestResults::new()
+            .ok("attr::case_1_http_1_1_keep_alive")
+            .ok("attr::case_2_ident_wins")
+            .fail("attr::case_3_with_braces_fails")
+            .ok("attr::case_4")
+            .ok("list::case_1_old_syntax")
+            .ok("same_arity::case_1")
+            .ok("matrix::case_1_in_matrix::b_1")
+            .ok("matrix::case_1_in_matrix::b_2")
+            .assert(output);
+
+        assert_in!(out, "TEST START: with {braces} & fails! ");
+    }
+
     #[test]
     fn should_apply_partial_fixture() {
         let (output, _) = run_test(res("partial.rs"));
@@ -632,16 +691,32 @@ mod cases {
             );
         }
 
+        #[test]
+        fn with_a_leading_string_that_is_not_a_description() {
+            let (output, _) = execute();
+            let stderr = output.stderr.str();
+
+            assert_in!(
+                stderr,
+                r#"
+                   |
+                13 | #[case("Not a description", 42)]
+                   |        ^^^^^^^^^^^^^^^^^^^^^^^
+                "#
+                .unindent()
+            );
+        }
+
         #[test]
         fn and_reports_all_errors() {
             let (output, _) = execute();
             let stderr = output.stderr.str();
 
-            // Exactly 4 cases are wrong
+            // Exactly 5 cases are wrong
             assert_eq!(
-                4,
+                5,
                 stderr.count("Wrong case signature: should match the given parameters list."),
-                "Should contain message exactly 4 occurrences in error message:\n{}",
+                "Should contain message exactly 5 occurrences in error message:\n{}",
                 stderr
             );
         }
@@ -700,6 +775,72 @@ mod cases {
     }
 }
 
+#[test]
+fn files() {
+    let prj = prj("files.rs");
+    let files = [
+        ("element_0.txt", "element_0.txt"),
+        ("element_1.txt", "element_1.txt"),
+        ("not_element.txt", "Wrong content"),
+        ("sub/element_2.txt", "element_2.txt"),
+        (".hidden.txt", ".hidden.txt"),
+    ];
+    for (name, content) in files {
+        let path = prj.path().join("files").join(name);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("start_with_name::path_1_element_0_txt")
+        .ok("start_with_name::path_2_element_1_txt")
+        .fail("start_with_name::path_3_not_element_txt")
+        .ok("start_with_name::path_4_element_2_txt")
+        .ok("exclude::path_1_element_0_txt")
+        .ok("exclude::path_2_element_1_txt")
+        .ok("exclude::path_3_element_2_txt")
+        .ok("include_dot_files::path_1__hidden_txt")
+        .ok("include_dot_files::path_2_element_0_txt")
+        .ok("include_dot_files::path_3_element_1_txt")
+        .fail("include_dot_files::path_4_not_element_txt")
+        .ok("include_dot_files::path_5_element_2_txt")
+        .ok("more_globs::path_1_element_0_txt::_n_1")
+        .ok("more_globs::path_1_element_0_txt::_n_2")
+        .ok("more_globs::path_2_element_2_txt::_n_1")
+        .ok("more_globs::path_2_element_2_txt::_n_2")
+        .ok("embed_str::content_1_element_0_txt")
+        .ok("embed_str::content_2_element_1_txt")
+        .fail("embed_str::content_3_not_element_txt")
+        .ok("embed_bytes::content_1_element_0_txt")
+        .ok("embed_bytes::content_2_element_1_txt")
+        .fail("embed_bytes::content_3_not_element_txt")
+        .assert(output);
+}
+
+#[test]
+fn auto_names() {
+    let (output, _) = run_test("auto_names.rs");
+
+    TestResults::new()
+        .ok("cases::case_1_0_0")
+        .ok("cases::case_2_1_1")
+        .ok("cases::case_3_forty_two")
+        .ok("cases::case_4_11")
+        .ok("values::addr_127_0_0_1_8000::n_first")
+        .ok("values::addr_127_0_0_1_8000::n_2")
+        .ok("values::addr_10_0_0_1_8000::n_first")
+        .ok("values::addr_10_0_0_1_8000::n_2")
+        .ok("values::addr_1_8000::n_first")
+        .ok("values::addr_1_8000::n_2")
+        .ok("not_enabled::name_1")
+        .ok("not_enabled::name_2")
+        .ok("argument_named_auto_names::case_1")
+        .ok("argument_named_auto_names::case_2")
+        .assert(output);
+}
+
 mod matrix {
     use super::*;
 
@@ -820,6 +961,101 @@ mod matrix {
             .ok("second::expected_2::input_2")
             .assert(output);
     }
+
+    #[test]
+    fn use_case_values_overrides() {
+        let (output, _) = run_test(res("case_overrides.rs"));
+
+        TestResults::new()
+            .ok("overrides::case_1::mode_1::n_1")
+            .ok("overrides::case_1::mode_1::n_2")
+            .ok("overrides::case_1::mode_2::n_1")
+            .ok("overrides::case_1::mode_2::n_2")
+            .ok("overrides::case_2::mode_1::n_1")
+            .ok("overrides::case_2::mode_1::n_2")
+            .ok("overrides::case_2::mode_2::n_1")
+            .ok("overrides::case_2::mode_2::n_2")
+            .ok("overrides::case_3::mode_1::n_1")
+            .ok("overrides::case_3::mode_1::n_2")
+            .ok("overrides::case_4_replaced::mode_1::n_1")
+            .ok("overrides::case_4_replaced::mode_1::n_2")
+            .ok("list::case_1::mode_1")
+            .ok("list::case_1::mode_2")
+            .ok("list::case_2::mode_1")
+            .assert(output);
+    }
+
+    #[test]
+    fn zip_and_pairwise_modes() {
+        let (output, _) = run_test(res("modes.rs"));
+
+        TestResults::new()
+            .ok("zip::a_1::b_1")
+            .ok("zip::a_2::b_2")
+            .ok("zip::a_3::b_3")
+            .ok("zip_cases::case_1::a_1::b_1")
+            .ok("zip_cases::case_1::a_2::b_2")
+            .ok("zip_cases::case_2::a_1::b_1")
+            .ok("zip_cases::case_2::a_2::b_2")
+            .ok("zip_list::a_1::b_1")
+            .ok("zip_list::a_2::b_2")
+            .ok("pairwise::a_1::b_1::c_1")
+            .ok("pairwise::a_1::b_2::c_2")
+            .ok("pairwise::a_2::b_1::c_2")
+            .ok("pairwise::a_2::b_2::c_1")
+            .assert(output);
+    }
+
+    #[test]
+    fn exclude_combinations() {
+        let (output, _) = run_test(res("exclude.rs"));
+
+        TestResults::new()
+            .ok("exclude::mode_1::n_1")
+            .ok("exclude::mode_1::n_2")
+            .ok("exclude::mode_2::n_1")
+            .ok("exclude_labels::mode_2::n_2")
+            .ok("exclude_cases::case_1::mode_2")
+            .ok("exclude_cases::case_2::mode_2")
+            .assert(output);
+    }
+
+    #[test]
+    fn values_from_const() {
+        let (output, _) = run_test(res("values_from.rs"));
+
+        TestResults::new()
+            .ok("const_array::n_1")
+            .ok("const_array::n_2")
+            .ok("const_array::n_3")
+            .ok("const_slice::name_1::times_1")
+            .ok("const_slice::name_1::times_2")
+            .ok("const_slice::name_2::times_1")
+            .ok("const_slice::name_2::times_2")
+            .ok("const_slice::name_3::times_1")
+            .fail("const_slice::name_3::times_2")
+            .ok("not_copy::range_1")
+            .fail("not_copy::range_2")
+            .assert(output);
+    }
+
+    #[test]
+    fn use_value_labels_as_names() {
+        let (output, _) = run_test(res("labels.rs"));
+
+        TestResults::new()
+            .fail("attr::name_ascii::age_min")
+            .ok("attr::name_ascii::age_2")
+            .ok("attr::name_unicode::age_min")
+            .ok("attr::name_unicode::age_2")
+            .ok("attr::name_empty::age_min")
+            .ok("attr::name_empty::age_2")
+            .fail("list::name_ascii::age_min")
+            .ok("list::name_ascii::age_max")
+            .ok("list::name_empty::age_min")
+            .ok("list::name_empty::age_max")
+            .assert(output);
+    }
 }
 
 #[test]
@@ -895,6 +1131,47 @@ fn ignore_underscore_args() {
         .assert(output);
 }
 
+#[test]
+fn strategy() {
+    let (output, _) = run_test("strategy.rs");
+    let out = output.stdout.str().to_string();
+
+    TestResults::new()
+        .ok("inject_fixtures")
+        .fail("fail_and_shrink")
+        .ok("more_strategies")
+        .ok("sorted")
+        .fail("return_result")
+        .ok("cases::case_1_one")
+        .fail("cases::case_2_zero")
+        .ok("matrix::m_1")
+        .fail("matrix::m_2")
+        .assert(output);
+
+    assert_in!(out, "minimal failing input = (500,)");
+    assert_in!(out, "minimal failing input = (50,)");
+    assert_in!(out, "minimal failing input = (1,)");
+    assert_in!(out, "minimal failing input = (67,)");
+    assert_in!(out, "(rerun with RSTEST_SEED=");
+}
+
+#[test]
+fn test_rng() {
+    let (output, _) = run_test("test_rng.rs");
+    let out = output.stdout.str().to_string();
+
+    TestResults::new()
+        .ok("seeded_by_test_name")
+        .ok("fixtures_use_the_same_seed")
+        .fail("fail::case_1_one")
+        .fail("fail::case_2_two")
+        .assert(output);
+
+    assert_in!(out, "TEST ARGUMENTS");
+    assert_in!(out, "test_rng = TestRng { seed: ");
+    assert_in!(out, "(RSTEST_SEED=");
+}
+
 #[test]
 fn timeout() {
     let prj = prj("timeout.rs");
@@ -935,6 +1212,107 @@ fn timeout() {
         .assert(output);
 }
 
+#[test]
+fn timeout_cancel_sync_tests() {
+    let (output, _) = run_test("timeout_cancel.rs");
+    let out = output.stdout.str().to_string();
+
+    TestResults::new()
+        .fail("sleep_forever")
+        .fail("busy_loop")
+        .ok("pass")
+        .assert(output);
+
+    assert_eq!(2, out.matches("Resource released").count());
+}
+
+#[test]
+fn timeout_watchdog_with_not_send_fixtures() {
+    let (output, _) = run_test("timeout_watchdog.rs");
+
+    TestResults::new()
+        .ok("pass")
+        .fail("fail_timeout")
+        .fail("fail_value")
+        .ok("cases::case_1_pass")
+        .fail("cases::case_2_fail")
+        .fail("fail_never_stops")
+        .ok("pass_result")
+        .fail("fail_result")
+        .assert(output);
+}
+
+#[test]
+fn timeout_report() {
+    let prj = prj("timeout_report.rs");
+    prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+    let output = prj.run_tests().unwrap();
+    let out = output.stdout.str().to_string();
+
+    TestResults::new()
+        .ok("sync_test::case_1_fast")
+        .fail("sync_test::case_2_stuck")
+        .fail("async_test::delay_ms_1")
+        .assert(output);
+
+    for expected in [
+        "Timeout 50ms expired",
+        "test:     rstest_timeout_report::sync_test::case_2_stuck",
+        "delay = 60s",
+        "state:    stopped at checkpoint()",
+        "stack backtrace:",
+        "test:     rstest_timeout_report::async_test::delay_ms_1",
+        "delay_ms = 1000",
+        "state:    future dropped",
+    ] {
+        assert_in!(out, expected);
+    }
+}
+
+#[test]
+fn retries() {
+    let prj = prj("retries.rs");
+    prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+    let output = prj.run_tests().unwrap();
+    let out = output.stdout.str().to_string();
+
+    TestResults::new()
+        .ok("flaky")
+        .fail("always_fail")
+        .ok("resolve_fixtures_every_attempt")
+        .ok("retry_errors")
+        .ok("cases::case_1_retried")
+        .fail("cases::case_2_no_retries")
+        .ok("async_flaky")
+        .assert(output);
+
+    assert_in!(out, "---------- ATTEMPT 1/3 FAILED ----------");
+    assert_in!(out, "---------- ATTEMPT 2/3 FAILED ----------");
+    assert_in!(out, "attempt 3 failed");
+    // Every attempt of always_fail and the single one of case_2_no_retries
+    assert_eq!(4, out.matches(" TEST START ").count());
+}
+
+#[test]
+fn default_timeout() {
+    let prj = prj("default_timeout.rs").with_env("RSTEST_TIMEOUT", "300ms");
+    prj.add_dependency("async-std", r#"{version="*", features=["attributes"]}"#);
+    let output = prj.run_tests().unwrap();
+
+    TestResults::new()
+        .ok("pass")
+        .fail("fail_cancelled")
+        .ok("opt_out")
+        .ok("explicit_timeout")
+        .ok("cases::case_1_pass")
+        .ok("cases::case_2_opt_out")
+        .fail("cases::case_3_fail")
+        .fail("async_fail")
+        .fail("fail_never_stops")
+        .ok("pass_not_send_result")
+        .assert(output);
+}
+
 mod async_timeout_feature {
     use super::*;
 
@@ -978,6 +1356,25 @@ mod async_timeout_feature {
 
         TestResults::new().ok("single_pass").assert(output);
     }
+
+    #[test]
+    fn should_use_tokio_timer_if_tokio_timeout_feature_enabled() {
+        let prj = build_prj(&["tokio-timeout"]);
+        prj.add_dependency(
+            "tokio",
+            r#"{version="1", features=["rt", "macros", "time", "test-util"]}"#,
+        );
+
+        let output = prj
+            .set_code_file(resources("timeout_tokio.rs"))
+            .run_tests()
+            .unwrap();
+
+        TestResults::new()
+            .ok("paused_clock_pass")
+            .fail("paused_clock_fail")
+            .assert(output);
+    }
 }
 
 mod should_show_correct_errors {
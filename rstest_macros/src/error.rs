@@ -2,12 +2,12 @@
 use std::collections::HashMap;
 
 use proc_macro2::TokenStream;
-use syn::{spanned::Spanned, visit::Visit};
-use syn::{visit, ItemFn};
+use syn::{spanned::Spanned, ItemFn};
 
 use crate::parse::{
     fixture::FixtureInfo,
-    rstest::{RsTestData, RsTestInfo},
+    rstest::{MatrixMode, RsTestAttributes, RsTestData, RsTestInfo},
+    vlist::ValueList,
 };
 use crate::refident::MaybeIdent;
 
@@ -18,6 +18,10 @@ pub(crate) fn rstest(test: &ItemFn, info: &RsTestInfo) -> TokenStream {
         .chain(duplicate_arguments(info.data.items.iter()))
         .chain(invalid_cases(&info.data))
         .chain(case_args_without_cases(&info.data))
+        .chain(invalid_matrix_mode(&info.attributes))
+        .chain(zip_length_mismatch(info))
+        .chain(invalid_exclusions(&info.data))
+        .chain(invalid_strategies(test, info))
         .map(|e| e.to_compile_error())
         .collect()
 }
@@ -25,55 +29,52 @@ pub(crate) fn rstest(test: &ItemFn, info: &RsTestInfo) -> TokenStream {
 pub(crate) fn fixture(test: &ItemFn, info: &FixtureInfo) -> TokenStream {
     missed_arguments(test, info.data.items.iter())
         .chain(duplicate_arguments(info.data.items.iter()))
-        .chain(async_once(test, info))
-        .chain(generics_once(test, info))
+        .chain(teardown_once(info))
+        .chain(scope_once(info))
+        .chain(scope_teardown(info))
+        .chain(once_reset_without_value(test, info))
         .map(|e| e.to_compile_error())
         .collect()
 }
 
-fn async_once<'a>(test: &'a ItemFn, info: &FixtureInfo) -> Errors<'a> {
-    match (test.sig.asyncness, info.attributes.get_once()) {
-        (Some(_asyncness), Some(once)) => Box::new(std::iter::once(syn::Error::new(
+fn teardown_once<'a>(info: &FixtureInfo) -> Errors<'a> {
+    match (info.attributes.get_teardown(), info.attributes.get_once()) {
+        (Some(_teardown), Some(once)) => Box::new(std::iter::once(syn::Error::new(
             once.span(),
-            "Cannot apply #[once] to async fixture.",
+            "Cannot apply #[teardown] to #[once] fixture.",
         ))),
         _ => Box::new(std::iter::empty()),
     }
 }
 
-#[derive(Default)]
-struct SearchImpl(bool);
-
-impl<'ast> Visit<'ast> for SearchImpl {
-    fn visit_type(&mut self, i: &'ast syn::Type) {
-        if self.0 {
-            return;
-        }
-        if let syn::Type::ImplTrait(_) = i {
-            self.0 = true
-        }
-        visit::visit_type(self, i);
+fn scope_once<'a>(info: &FixtureInfo) -> Errors<'a> {
+    match (info.attributes.get_scope(), info.attributes.get_once()) {
+        (Some(_scope), Some(once)) => Box::new(std::iter::once(syn::Error::new(
+            once.span(),
+            "Cannot apply #[scope] to #[once] fixture.",
+        ))),
+        _ => Box::new(std::iter::empty()),
     }
 }
 
-impl SearchImpl {
-    fn function_has_some_impl(f: &ItemFn) -> bool {
-        let mut s = SearchImpl::default();
-        visit::visit_item_fn(&mut s, f);
-        s.0
+fn scope_teardown<'a>(info: &FixtureInfo) -> Errors<'a> {
+    match (info.attributes.get_scope(), info.attributes.get_teardown()) {
+        (Some(scope), Some(_teardown)) => Box::new(std::iter::once(syn::Error::new(
+            scope.span(),
+            "Cannot apply #[teardown] to #[scope] fixture.",
+        ))),
+        _ => Box::new(std::iter::empty()),
     }
 }
 
-fn has_some_generics(test: &ItemFn) -> bool {
-    !test.sig.generics.params.is_empty() || SearchImpl::function_has_some_impl(test)
-}
-
-fn generics_once<'a>(test: &'a ItemFn, info: &FixtureInfo) -> Errors<'a> {
-    match (has_some_generics(test), info.attributes.get_once()) {
-        (true, Some(once)) => Box::new(std::iter::once(syn::Error::new(
-            once.span(),
-            "Cannot apply #[once] on generic fixture.",
-        ))),
+fn once_reset_without_value<'a>(test: &ItemFn, info: &FixtureInfo) -> Errors<'a> {
+    match (&test.sig.output, info.attributes.get_once()) {
+        (syn::ReturnType::Default, Some(once)) if info.attributes.is_once_reset() => {
+            Box::new(std::iter::once(syn::Error::new(
+                once.span(),
+                "#[once(reset)] fixture should return a value.",
+            )))
+        }
         _ => Box::new(std::iter::empty()),
     }
 }
@@ -228,46 +229,367 @@ fn case_args_without_cases(params: &RsTestData) -> Errors {
     Box::new(std::iter::empty())
 }
 
+fn invalid_matrix_mode(attributes: &RsTestAttributes) -> Errors<'_> {
+    Box::new(
+        attributes
+            .matrix_modes()
+            .enumerate()
+            .filter_map(|(pos, (attr, args))| match args.as_slice() {
+                _ if pos > 0 => Some(syn::Error::new(
+                    attr.span(),
+                    "You cannot use #[matrix] more than once",
+                )),
+                [mode] if mode.to_string().parse::<MatrixMode>().is_ok() => None,
+                [mode] => Some(syn::Error::new(
+                    mode.span(),
+                    format!(
+                        "Invalid matrix mode: valid modes are {}",
+                        MatrixMode::MODES.join(", ")
+                    ),
+                )),
+                _ => Some(syn::Error::new(
+                    attr.span(),
+                    "#[matrix] needs exactly one mode: e.g. #[matrix(zip)]",
+                )),
+            }),
+    )
+}
+
+fn zip_length_mismatch(info: &RsTestInfo) -> Errors<'_> {
+    if info.attributes.matrix_mode() != MatrixMode::Zip {
+        return Box::new(std::iter::empty());
+    }
+    let defaults = zip_mismatches(info.data.list_values().collect())
+        .into_iter()
+        .map(|(first, other)| syn::Error::new(other.arg.span(), zip_mismatch_message(first, other)))
+        .collect::<Vec<_>>();
+    // Overrides can break the lists length just in a case: report it once on the case
+    let overrides = info
+        .data
+        .cases()
+        .filter(|case| !case.overrides.is_empty())
+        .filter_map(|case| {
+            let lists = info
+                .data
+                .list_values()
+                .map(|vlist| case.values_override(&vlist.arg).unwrap_or(vlist))
+                .collect();
+            zip_mismatches(lists)
+                .into_iter()
+                .next()
+                .map(|(first, other)| {
+                    syn::Error::new(
+                        case.overrides[0].arg.span(),
+                        zip_mismatch_message(first, other),
+                    )
+                })
+        })
+        .collect::<Vec<_>>();
+
+    Box::new(defaults.into_iter().chain(overrides))
+}
+
+fn invalid_exclusions(params: &RsTestData) -> Errors<'_> {
+    Box::new(
+        params
+            .exclusions()
+            .flat_map(|exclusion| exclusion.conditions.iter())
+            .filter_map(move |condition| {
+                let mut lists = params
+                    .list_values()
+                    .filter(|vlist| vlist.arg == condition.arg)
+                    .peekable();
+                if lists.peek().is_none() {
+                    return Some(syn::Error::new(
+                        condition.arg.span(),
+                        format!(
+                            "Cannot exclude '{}': it is not a values list argument",
+                            condition.arg
+                        ),
+                    ));
+                }
+                let overrides = params
+                    .cases()
+                    .filter_map(|case| case.values_override(&condition.arg));
+                let mut values = lists.chain(overrides).flat_map(|vlist| vlist.values.iter());
+                if values.any(|v| condition.matches(v)) {
+                    None
+                } else {
+                    Some(syn::Error::new_spanned(
+                        &condition.value,
+                        format!("This value is not in the '{}' values list", condition.arg),
+                    ))
+                }
+            }),
+    )
+}
+
+fn invalid_strategies<'a>(test: &ItemFn, info: &'a RsTestInfo) -> Errors<'a> {
+    let is_async = test.sig.asyncness.is_some();
+    let strategies = info.attributes.strategies().collect::<Vec<_>>();
+    Box::new(
+        strategies
+            .clone()
+            .into_iter()
+            .enumerate()
+            .filter_map(move |(pos, (arg, _))| {
+                let message = if is_async {
+                    "#[strategy] cannot be used in async tests".to_string()
+                } else if strategies[..pos].iter().any(|(prev, _)| prev == &arg) {
+                    format!("Duplicate #[strategy] on '{}'", arg)
+                } else if info
+                    .data
+                    .items
+                    .iter()
+                    .filter_map(MaybeIdent::maybe_ident)
+                    .any(|id| id == arg)
+                {
+                    format!(
+                        "Cannot generate '{}' by #[strategy]: it's already a fixture, case or values list argument",
+                        arg
+                    )
+                } else {
+                    return None;
+                };
+                Some(syn::Error::new(arg.span(), message))
+            }),
+    )
+}
+
+fn zip_mismatches(lists: Vec<&ValueList>) -> Vec<(&ValueList, &ValueList)> {
+    let first = match lists.first() {
+        Some(&first) => first,
+        None => return vec![],
+    };
+    lists
+        .into_iter()
+        .filter(|other| other.values.len() != first.values.len())
+        .map(|other| (first, other))
+        .collect()
+}
+
+fn zip_mismatch_message(first: &ValueList, other: &ValueList) -> String {
+    format!(
+        "All values lists should have the same length in #[matrix(zip)]: '{}' has {} values but '{}' has {}",
+        first.arg,
+        first.values.len(),
+        other.arg,
+        other.values.len()
+    )
+}
+
 #[cfg(test)]
 mod test {
+    use crate::parse::ExtendWithFunctionAttrs;
     use crate::test::{assert_eq, *};
     use rstest_test::assert_in;
 
     use super::*;
 
+    #[test]
+    fn teardown_once_should_return_error() {
+        let info = FixtureInfo::default()
+            .with_once()
+            .with_teardown("|_| || ()");
+
+        let errors = teardown_once(&info);
+
+        let out = errors.map(|e| format!("{:?}", e)).collect::<String>();
+
+        assert_in!(out, "Cannot apply #[teardown] to #[once] fixture.");
+    }
+
+    #[rstest]
+    #[case::just_once(FixtureInfo::default().with_once())]
+    #[case::just_teardown(FixtureInfo::default().with_teardown("|_| || ()"))]
+    fn teardown_once_should_not_return_error_if_not_both(#[case] info: FixtureInfo) {
+        let errors = teardown_once(&info);
+
+        assert_eq!(0, errors.count());
+    }
+
+    #[test]
+    fn scope_once_should_return_error() {
+        let info = FixtureInfo::default().with_once().with_module_scope();
+
+        let errors = scope_once(&info);
+
+        let out = errors.map(|e| format!("{:?}", e)).collect::<String>();
+
+        assert_in!(out, "Cannot apply #[scope] to #[once] fixture.");
+    }
+
+    #[rstest]
+    #[case::just_once(FixtureInfo::default().with_once())]
+    #[case::just_scope(FixtureInfo::default().with_module_scope())]
+    fn scope_once_should_not_return_error_if_not_both(#[case] info: FixtureInfo) {
+        let errors = scope_once(&info);
+
+        assert_eq!(0, errors.count());
+    }
+
+    #[test]
+    fn scope_teardown_should_return_error() {
+        let info = FixtureInfo::default()
+            .with_module_scope()
+            .with_teardown("|_| || ()");
+
+        let errors = scope_teardown(&info);
+
+        let out = errors.map(|e| format!("{:?}", e)).collect::<String>();
+
+        assert_in!(out, "Cannot apply #[teardown] to #[scope] fixture.");
+    }
+
+    #[rstest]
+    #[case::just_scope(FixtureInfo::default().with_module_scope())]
+    #[case::just_teardown(FixtureInfo::default().with_teardown("|_| || ()"))]
+    fn scope_teardown_should_not_return_error_if_not_both(#[case] info: FixtureInfo) {
+        let errors = scope_teardown(&info);
+
+        assert_eq!(0, errors.count());
+    }
+
+    fn rstest_info(code: &str) -> RsTestInfo {
+        let mut item_fn = code.ast();
+        let mut info = RsTestInfo::default();
+        info.extend_with_function_attrs(&mut item_fn).unwrap();
+        info
+    }
+
+    #[rstest]
+    #[case::invalid(
+        "#[matrix(all)] fn f(#[values(1)] a: u32) {}",
+        "Invalid matrix mode: valid modes are product, zip, pairwise"
+    )]
+    #[case::more_than_once(
+        "#[matrix(zip)] #[matrix(zip)] fn f(#[values(1)] a: u32) {}",
+        "You cannot use #[matrix] more than once"
+    )]
+    fn invalid_matrix_mode_should_return_error(#[case] code: &str, #[case] message: &str) {
+        let info = rstest_info(code);
+
+        let out = invalid_matrix_mode(&info.attributes)
+            .map(|e| format!("{:?}", e))
+            .collect::<String>();
+
+        assert_in!(out, message);
+    }
+
+    #[rstest]
+    #[case::values(r#"#[matrix(zip)] fn f(#[values(1, 2)] a: u32, #[values(1)] b: u32) {}"#)]
+    #[case::overrides(
+        r#"#[matrix(zip)] #[case(1)] #[case(2, a = [1])]
+        fn f(#[case] c: u32, #[values(1, 2)] a: u32, #[values(1, 2)] b: u32) {}"#
+    )]
+    fn zip_length_mismatch_should_return_error(#[case] code: &str) {
+        let info = rstest_info(code);
+
+        let out = zip_length_mismatch(&info)
+            .map(|e| format!("{:?}", e))
+            .collect::<String>();
+
+        assert_in!(
+            out,
+            "All values lists should have the same length in #[matrix(zip)]"
+        );
+    }
+
+    #[rstest]
+    #[case::not_a_values_list(
+        r#"#[exclude(a = 1, c = 2)] fn f(#[values(1, 2)] a: u32, c: u32) {}"#,
+        "Cannot exclude 'c': it is not a values list argument"
+    )]
+    #[case::unknown_value(
+        r#"#[exclude(a = 3)] fn f(#[values(1, 2)] a: u32) {}"#,
+        "This value is not in the 'a' values list"
+    )]
+    fn invalid_exclusions_should_return_error(#[case] code: &str, #[case] message: &str) {
+        let info = rstest_info(code);
+
+        let out = invalid_exclusions(&info.data)
+            .map(|e| format!("{:?}", e))
+            .collect::<String>();
+
+        assert_in!(out, message);
+    }
+
+    #[rstest]
+    #[case::value(r#"#[exclude(a = 2)] fn f(#[values(1, 2)] a: u32) {}"#)]
+    #[case::label(r#"#[exclude(a = two)] fn f(#[values(1, two => 2)] a: u32) {}"#)]
+    #[case::override_value(
+        r#"#[exclude(a = 3)] #[case(1, a = [3])] fn f(#[case] c: u32, #[values(1, 2)] a: u32) {}"#
+    )]
+    fn valid_exclusions_should_not_return_error(#[case] code: &str) {
+        let info = rstest_info(code);
+
+        assert_eq!(0, invalid_exclusions(&info.data).count());
+    }
+
     #[rstest]
-    #[case::generics("fn f<G: SomeTrait>(){}")]
-    #[case::const_generics("fn f<const N: usize>(){}")]
-    #[case::lifetimes("fn f<'a>(){}")]
-    #[case::use_impl_in_answer("fn f() -> impl Iterator<Item=u32>{}")]
-    #[case::use_impl_in_argumets("fn f(it: impl Iterator<Item=u32>){}")]
-    #[should_panic]
-    #[case::sanity_check_with_no_generics("fn f() {}")]
-    fn generics_once_should_return_error(#[case] f: &str) {
-        let f: ItemFn = f.ast();
-        let info = FixtureInfo::default().with_once();
-
-        let errors = generics_once(&f, &info);
-
-        let out = errors
+    #[case::async_test(
+        "async fn f(#[strategy(0..10)] a: u32) {}",
+        "cannot be used in async tests"
+    )]
+    #[case::duplicate(
+        "fn f(#[strategy(0..10)] #[strategy(0..5)] a: u32) {}",
+        "Duplicate #[strategy] on 'a'"
+    )]
+    #[case::case_arg(
+        "#[case(1)] fn f(#[case] #[strategy(0..10)] a: u32) {}",
+        "Cannot generate 'a' by #[strategy]"
+    )]
+    #[case::values_list(
+        "fn f(#[values(1, 2)] #[strategy(0..10)] a: u32) {}",
+        "Cannot generate 'a' by #[strategy]"
+    )]
+    fn invalid_strategies_should_return_error(#[case] code: &str, #[case] message: &str) {
+        let test = code.ast();
+        let info = rstest_info(code);
+
+        let out = invalid_strategies(&test, &info)
             .map(|e| format!("{:?}", e))
-            .collect::<Vec<_>>()
-            .join("-----------------------\n");
+            .collect::<String>();
+
+        assert_in!(out, message);
+    }
+
+    #[test]
+    fn valid_strategies_should_not_return_error() {
+        let code = "#[case(1)] fn f(#[case] c: u32, #[strategy(0..10)] a: u32, b: u32) {}";
+        let test = code.ast();
+        let info = rstest_info(code);
+
+        assert_eq!(0, invalid_strategies(&test, &info).count());
+    }
+
+    #[test]
+    fn zip_length_mismatch_should_not_return_error_for_other_modes() {
+        let info = rstest_info(r#"fn f(#[values(1, 2)] a: u32, #[values(1)] b: u32) {}"#);
+
+        assert_eq!(0, zip_length_mismatch(&info).count());
+    }
+
+    #[test]
+    fn once_reset_without_value_should_return_error() {
+        let test = "fn f() {}".ast();
+        let info = FixtureInfo::default().with_once_reset();
+
+        let errors = once_reset_without_value(&test, &info);
+
+        let out = errors.map(|e| format!("{:?}", e)).collect::<String>();
 
-        assert_in!(out, "Cannot apply #[once] on generic fixture.");
+        assert_in!(out, "#[once(reset)] fixture should return a value.");
     }
 
     #[rstest]
-    #[case::generics("fn f<G: SomeTrait>(){}")]
-    #[case::const_generics("fn f<const N: usize>(){}")]
-    #[case::lifetimes("fn f<'a>(){}")]
-    #[case::use_impl_in_answer("fn f() -> impl Iterator<Item=u32>{}")]
-    #[case::use_impl_in_argumets("fn f(it: impl Iterator<Item=u32>){}")]
-    fn generics_once_should_not_return_if_no_once(#[case] f: &str) {
-        let f: ItemFn = f.ast();
-        let info = FixtureInfo::default();
-
-        let errors = generics_once(&f, &info);
+    #[case::reset_with_value("fn f() -> u32 { 42 }", FixtureInfo::default().with_once_reset())]
+    #[case::just_once("fn f() {}", FixtureInfo::default().with_once())]
+    fn once_reset_without_value_should_not_return_error(
+        #[case] test: &str,
+        #[case] info: FixtureInfo,
+    ) {
+        let errors = once_reset_without_value(&test.ast(), &info);
 
         assert_eq!(0, errors.count());
     }
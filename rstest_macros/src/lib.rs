@@ -143,7 +143,9 @@ use quote::ToTokens;
 /// for every tests. `rstest` provides `#[once]` attribute for these cases.
 ///
 /// If you mark your fixture with this attribute and `rstest` will compute a static reference to your
-/// fixture result and return this reference to all your tests that need this fixture.
+/// fixture result and return this reference to all your tests that need this fixture. Tests can
+/// run in different threads, so the fixture's type must be `Send + Sync`: otherwise you'll get
+/// a compile error that point to the fixture's return type.
 ///
 /// In follow example all tests share the same reference to the `42` static value.
 ///
@@ -170,13 +172,182 @@ use quote::ToTokens;
 /// }
 /// ```
 ///
-/// There are some limitations when you use `#[once]` fixture. `rstest` forbid to use once fixture
-/// for:
+/// You can use `#[once]` also on `async` fixtures: the first test that awaits the fixture
+/// computes the value and all the other tests wait for it and then get the same `&'static`
+/// reference. The async implementation doesn't depend on any runtime, so you can
+/// use it with any test attribute (`#[tokio::test]`, `#[actix_rt::test]`, ...). Take care
+/// that the fixture value must not depend on the runtime of the first test, that will be stopped
+/// when the test is terminated (for instance don't spawn tasks on it).
 ///
-/// - `async` function
-/// - Generic function (both with generic types or use `impl` trait)
+/// ```rust,ignore
+/// use rstest::*;
+///
+/// #[fixture]
+/// #[once]
+/// async fn once_fixture() -> i32 { 42 }
+///
+/// #[rstest]
+/// async fn single(#[future] once_fixture: &i32) {
+///     assert_eq!(&42, once_fixture.await)
+/// }
+/// ```
 ///
-/// Take care that the `#[once]` fixture value will **never dropped**.
+/// `#[once]` fixtures can be also generic (both with generic types or use `impl` trait): in this
+/// case `rstest` computes a value for every combination of concrete generic parameters (also
+/// the ones that don't appear in the returned type) and all tests that use the same combination
+/// share the same reference. The fixture's type should be `Send + Sync + 'static` and all generic
+/// types should be `'static`.
+///
+/// ```rust,ignore
+/// use rstest::*;
+/// # trait Repository {}
+/// # #[derive(Default)] struct InMemory;
+/// # impl Repository for InMemory {}
+/// # #[derive(Default)] struct OnDisk;
+/// # impl Repository for OnDisk {}
+///
+/// #[fixture]
+/// #[once]
+/// fn repository<R: Repository + Default>() -> R {
+///     R::default()
+/// }
+///
+/// #[rstest]
+/// fn in_memory(repository: &InMemory) {
+///     // Share the `InMemory` instance with all tests that use `&InMemory`
+/// }
+///
+/// #[rstest]
+/// fn on_disk(repository: &OnDisk) {
+///     // Share the `OnDisk` instance with all tests that use `&OnDisk`
+/// }
+/// ```
+///
+/// Take care that the `#[once]` fixture value will **never dropped** by default. If you need
+/// to release it (for instance to flush a file or stop a server) you can use `#[once(drop)]`:
+/// the value will be dropped when the test process exits, in the reverse order of creation.
+/// In this case tests receive a `rstest::scope::Shared<T>` instead of `&T`: if a thread
+/// that is still alive at exit (like the one of a timed out test) holds a copy of it, the
+/// value is not dropped till the last copy is dropped.
+///
+/// ```rust,ignore
+/// use rstest::*;
+/// use rstest::scope::Shared;
+/// # struct Server;
+/// # impl Server { fn start() -> Self { Server } }
+///
+/// #[fixture]
+/// #[once(drop)]
+/// fn server() -> Server {
+///     Server::start()
+/// }
+///
+/// #[rstest]
+/// fn single(server: Shared<Server>) {
+///     // `Server` will be dropped when all tests are done
+/// }
+/// ```
+///
+/// If your `#[once]` fixture is a mutable shared service (like an in memory store behind a
+/// `Mutex`) tests can pollute it: use `#[once(reset)]` to build it just once but restore it
+/// to a clean state before every test that use it. The fixture's type should implement
+/// `rstest::Reset` (`Mutex<T>` and `RwLock<T>` implement it for every `T: Default`), and
+/// `rstest` generates a `reset()` hook in the fixture struct that is called every time a test
+/// receive the value. To keep tests isolated, tests that use `#[once(reset)]` fixtures
+/// don't run concurrently: they share a single lock, also when they use different fixtures.
+///
+/// ```rust,ignore
+/// use rstest::*;
+/// use std::sync::Mutex;
+///
+/// #[fixture]
+/// #[once(reset)]
+/// fn store() -> Mutex<Vec<u32>> {
+///     Default::default()
+/// }
+///
+/// #[rstest]
+/// #[case(1)]
+/// #[case(2)]
+/// fn push(store: &Mutex<Vec<u32>>, #[case] value: u32) {
+///     store.lock().unwrap().push(value);
+///     // The store is empty at the beginning of every test
+///     assert_eq!(vec![value], *store.lock().unwrap());
+/// }
+/// ```
+///
+/// # Module Scoped Fixture
+///
+/// Sometimes a fixture is too expensive to be computed for every test but you don't want to keep
+/// it alive for the whole process like `#[once]` does. A `#[scope(module)]` fixture is
+/// computed just once for all tests generated by the same `#[rstest]` function (every
+/// case and every matrix combination) and dropped when the last of these tests is terminated
+/// (`#[ignore]` cases are not taken into account, and if some tests are filtered out the value is
+/// dropped when the test process exits). `#[scope(module)]` fixtures cannot define a
+/// `#[teardown]`. The fixture's value is wrapped in `rstest::scope::Shared<T>` that implements `Deref<Target=T>`,
+/// and the fixture's type must be `Send + Sync` because tests can run in different threads.
+///
+/// ```rust,ignore
+/// use rstest::*;
+/// use rstest::scope::Shared;
+/// # struct Corpus;
+/// # impl Corpus { fn load() -> Self { Corpus } fn contains(&self, _w: &str) -> bool { true } }
+///
+/// #[fixture]
+/// #[scope(module)]
+/// fn corpus() -> Corpus {
+///     Corpus::load()
+/// }
+///
+/// #[rstest]
+/// #[case("first")]
+/// #[case("second")]
+/// #[case("third")]
+/// fn contains(corpus: Shared<Corpus>, #[case] word: &str) {
+///     // The corpus is loaded just once for all cases
+///     assert!(corpus.contains(word))
+/// }
+/// ```
+///
+/// If a test doesn't belong to an `#[rstest]` function with some cases or values (or you
+/// filter some tests out) it just gets its own value that will be dropped with the test.
+/// You cannot use `#[scope(...)]` with `#[once]`.
+///
+/// # Teardown
+///
+/// Sometimes dropping the fixture value is not enough to release all the resources that the
+/// fixture allocated: you can use `#[teardown(expr)]` attribute to define the code that should be
+/// executed when the test that used the fixture is terminated. `expr` is something like
+/// `FnOnce(&T) -> G` where `T` is the fixture's type and `G` implements
+/// `rstest::teardown::TearDown` trait: every `FnOnce()` closure is a valid `G`.
+///
+/// ```rust,ignore
+/// use rstest::*;
+/// # use std::path::PathBuf;
+/// # fn create_unique_dir() -> PathBuf { todo!() }
+///
+/// #[fixture]
+/// #[teardown(|dir| {
+///     let dir = dir.clone();
+///     move || std::fs::remove_dir_all(dir).unwrap()
+/// })]
+/// fn temp_dir() -> PathBuf {
+///     create_unique_dir()
+/// }
+///
+/// #[rstest]
+/// fn should_write_file(temp_dir: PathBuf) {
+///     std::fs::write(temp_dir.join("file.txt"), "content").unwrap();
+/// }
+/// ```
+///
+/// The teardown code is executed after every test also if the test panics, and the fixtures used
+/// by a test are teardown in reverse order: a fixture is teardown before the
+/// fixtures that it uses. You cannot use `#[teardown]` with `#[once]` fixtures.
+///
+/// The teardowns are tracked by thread: a fixture resolved in another thread than the test's
+/// one (e.g. in a task spawned on a multi thread tokio runtime) is not teardown at the end
+/// of the test but when its thread terminates.
 ///
 /// # Partial Injection
 ///
@@ -542,6 +713,55 @@ pub fn fixture(
 /// test result: ok. 4 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out
 /// ```
 ///
+/// If you prefer a free text you can start the case with a `desc = "..."` argument: the
+/// text should be a literal string, it is sanitized to compose the test name and it's
+/// shown in the `TEST START` banner of the test output.
+///
+/// ```rust,ignore
+/// # use rstest::*;
+/// #[rstest]
+/// #[case(desc = "http 1.1 keep-alive", "HTTP/1.1", true)]
+/// #[case(desc = "http 1.0 close", "HTTP/1.0", false)]
+/// fn keep_alive(#[case] version: &str, #[case] expected: bool) {
+///     assert_eq!(expected, is_keep_alive(version))
+/// }
+/// ```
+///
+/// Here the tests are `keep_alive::case_1_http_1_1_keep_alive` and
+/// `keep_alive::case_2_http_1_0_close` and the first one prints
+/// `--- TEST START: http 1.1 keep-alive ---`. If you use both `case::ident` and a
+/// text the ident is used for the test name.
+///
+/// ### Cases from a data file
+///
+/// If your test vectors live in a CSV, JSON or TOML file you can use
+/// `#[table("path")]` attribute (the path is relative to the crate root): the file
+/// is read at compile time and every row becomes a case.
+///
+/// ```rust,ignore
+/// # use rstest::*;
+/// # use std::net::SocketAddr;
+/// #[rstest]
+/// #[table("tests/vectors/addr.csv")]
+/// fn parse_address(#[case] addr: SocketAddr, #[case] port: u16) {
+///     assert_eq!(port, addr.port())
+/// }
+/// ```
+///
+/// The columns are mapped to the `#[case]` arguments by name and every value is
+/// injected as a literal string, so it's converted by the [magic
+/// conversion](#magic-conversion): a missing column is a compile error, other
+/// columns are ignored. If there is a `name` column (and it's not a
+/// case argument) it becomes the case's free text description, otherwise the cases
+/// are described by their row number (`parse_address::case_1_row_1`).
+///
+/// - CSV files should have a header with the column names; fields can be quoted by `"`
+/// - JSON files should contain an array of objects
+/// - TOML files should contain a `[[case]]` array of tables
+///
+/// JSON and TOML support should be enabled by `table-json` and `table-toml` features
+/// (disabled by default). The tests are compiled again when the file changes.
+///
 /// ### Use specific `case` attributes
 ///
 /// Every function's attributes that preceding a `#[case]` attribute will
@@ -649,6 +869,137 @@ pub fn fixture(
 /// Also value list implements the magic conversion feature: every time the value type
 /// implements `FromStr` trait you can use a literal string to define it.
 ///
+/// ### Label values
+///
+/// Names like `name_3::age_2` don't tell you which values failed: you can label a
+/// value by `label => value` or `#[name(label)] value` and the label replaces its
+/// index in the test name. The values without a label keep the positional index.
+///
+/// ```rust,ignore
+/// # use rstest::*;
+/// # fn valid_user(name: &str, age: u8) -> bool { true }
+/// #[rstest]
+/// fn should_accept(
+///     #[values(ascii => "abc", unicode => "ñ", #[name(empty)] "")]
+///     name: &str,
+///     #[values(min => 0, 42)]
+///     age: u8
+/// ) {
+///     assert!(valid_user(name, age))
+/// }
+/// ```
+///
+/// Here the tests are `should_accept::name_ascii::age_min`,
+/// `should_accept::name_ascii::age_2`, `should_accept::name_unicode::age_min` and so on.
+///
+/// ### Derive names from literals
+///
+/// With `#[rstest(auto_names)]` (or `::auto_names` in the attributes list) the
+/// names of cases and values are derived from their literal arguments: cases without
+/// a description get the sanitized literal arguments after the index and literal
+/// values replace their index. A bare `auto_names` is a case argument if the test
+/// function has an argument with this name.
+///
+/// ```rust,ignore
+/// # use rstest::*;
+/// # use std::net::SocketAddr;
+/// #[rstest(auto_names)]
+/// #[case(0, 0)]
+/// #[case("hello world", 11)]
+/// fn test(#[case] input: &str, #[case] expected: usize, #[values("127.0.0.1:8000")] addr: SocketAddr) {
+///     // ...
+/// }
+/// ```
+///
+/// generates `test::case_1_0_0::addr_127_0_0_1_8000` and
+/// `test::case_2_hello_world_11::addr_127_0_0_1_8000`. The derived names are
+/// truncated to 32 chars and when the values names are not unique `rstest` falls
+/// back to use the index.
+///
+/// The `auto-names` feature enables it for every test of the crate:
+///
+/// ```toml
+/// [dev-dependencies]
+/// rstest = { version = "0.15.0", features = ["auto-names"] }
+/// ```
+///
+/// Cargo unifies the features in the dependency graph, so a dependency that enables
+/// `auto-names` on `rstest` enables it also for your tests.
+///
+/// ## Files path as input arguments
+///
+/// If you need to run a test for every file in a directory you can use
+/// `#[files("glob")]` attribute: the glob patterns are resolved at compile time
+/// relative to the crate root (`CARGO_MANIFEST_DIR`) and the argument works like a
+/// values list of `PathBuf` with a value for each matched file (sorted by path).
+/// The test names are derived from the file names.
+///
+/// ```rust,ignore
+/// # use rstest::*;
+/// # use std::path::PathBuf;
+/// # fn parse(content: String) -> Result<(), ()> { Ok(()) }
+/// #[rstest]
+/// fn parse_golden_files(
+///     #[files("tests/data/**/*.json", exclude = "**/broken_*")] path: PathBuf
+/// ) {
+///     assert!(parse(std::fs::read_to_string(path).unwrap()).is_ok())
+/// }
+/// ```
+///
+/// Here the generated tests will be something like `parse_golden_files::path_1_first_json`,
+/// `parse_golden_files::path_2_second_json` and so on. You can use more than one glob
+/// pattern, `exclude = "glob"` to discard some files (the pattern is matched against the
+/// path relative to the crate root) and `include_dot_files` to also consider the files
+/// and directories that start with a dot (ignored by default).
+///
+/// With `mode = "str"` or `mode = "bytes"` the argument receives the file content
+/// instead of its path: the content is embedded in the test binary by `include_str!()` or
+/// `include_bytes!()`, so the tests don't need to access to the files at runtime (useful
+/// when cross compiling) and they are rebuilt when a file changes.
+///
+/// Also in the default mode every matched file is tracked and the tests are compiled
+/// again when its content changes. But a procedural macro cannot tell to cargo that
+/// the list of the files in a directory changed: to generate the tests again when you
+/// add or remove a file call `rstest::build::track_files()` from your build script (with
+/// `rstest` in `[build-dependencies]`).
+///
+/// ```rust,ignore
+/// // build.rs
+/// fn main() {
+///     rstest::build::track_files(["tests/data"]);
+/// }
+/// ```
+///
+/// ```rust,ignore
+/// # use rstest::*;
+/// # fn parse(content: &str) -> Result<(), ()> { Ok(()) }
+/// #[rstest]
+/// fn parse_golden_files(#[files("tests/data/*.json", mode = "str")] content: &str) {
+///     assert!(parse(content).is_ok())
+/// }
+/// ```
+///
+/// ### Values from a `const`
+///
+/// If the same values are shared with benchmarks or fuzz seeds you can keep them in a
+/// `const` array or slice and use `#[values_from(PATH, len = N)]` argument attribute:
+/// every test takes a clone of an element of the source. The macro cannot read the `const`, so
+/// you should give its length: a wrong `len` is a compile error.
+///
+/// ```rust,ignore
+/// # use rstest::*;
+/// # fn parse(n: i64) -> i64 { n }
+/// pub const CORNER_CASES: [i64; 4] = [i64::MIN, -1, 0, i64::MAX];
+///
+/// #[rstest]
+/// fn roundtrip(#[values_from(crate::CORNER_CASES, len = 4)] n: i64) {
+///     assert_eq!(n, parse(n))
+/// }
+/// ```
+///
+/// The tests are named by the element index (`roundtrip::n_1` ... `roundtrip::n_4`) and
+/// the elements are copied out of the source, so their type should be `Copy`.
+///
 /// ## Use Parametrize definition in more tests
 ///
 /// If you need to use a test list for more than one test you can use
@@ -785,6 +1136,123 @@ pub fn fixture(
 /// If you want to use `timeout` for `async` test you need to use `async-timeout`
 /// feature (enabled by default).
 ///
+/// When the timeout expires an async test is dropped with all its fixtures. With the
+/// `tokio-timeout` feature the tokio timer is used if the test runs in a tokio
+/// runtime, so `#[tokio::test(start_paused = true)]` tests work as expected.
+///
+/// A sync test runs in its own thread that cannot be killed: the thread is cancelled
+/// and stops by unwinding at the next `rstest::timeout::checkpoint()` or
+/// `rstest::timeout::sleep()` call, releasing its fixtures. The fixtures' teardowns
+/// run only after the thread is stopped: if it's still running a little after the
+/// timeout they are skipped, because the test could still be using the fixtures.
+///
+/// ```rust,ignore
+/// # use rstest::*;
+/// # use std::time::Duration;
+/// #[rstest]
+/// #[timeout(Duration::from_secs(1))]
+/// fn converge(mut solver: Solver) {
+///     while !solver.done() {
+///         rstest::timeout::checkpoint();
+///         solver.step();
+///     }
+/// }
+/// ```
+///
+/// The test's thread takes all the test's arguments, so they should be `Send` and
+/// `'static`. Use `mode = watchdog` for tests that take `Rc`, `RefCell` or other non
+/// `Send` values: the new thread resolves the test's fixtures too and the test is cancelled
+/// like above when the timeout expires. If it doesn't stop the test fails anyway and its
+/// thread is leaked: its fixtures are never torn down, but the other tests are not blocked.
+/// The test's output is wrapped in a `rstest::timeout::TestOutput`, so it doesn't need to
+/// be `Send` either.
+///
+/// ```rust,ignore
+/// # use rstest::*;
+/// # use std::{rc::Rc, time::Duration};
+/// #[rstest]
+/// #[timeout(Duration::from_secs(1), mode = watchdog)]
+/// fn render_scene(scene: Rc<Scene>) {
+///     assert!(render(&scene).is_ok());
+/// }
+/// ```
+///
+/// `mode = watchdog` is for sync tests only: async timeouts don't need `Send` arguments.
+///
+/// ### Default timeout
+///
+/// Set `RSTEST_TIMEOUT` environment variable (like `RSTEST_TIMEOUT=30s`, units are
+/// `ms`, `s`, `m` and `h`) to give a timeout to every test that doesn't have an
+/// explicit `#[timeout]`: it's read at runtime, so you can set it just in your CI.
+/// Use `#[no_timeout]` to opt out a test or just some cases.
+///
+/// ```rust,ignore
+/// # use rstest::*;
+/// #[rstest]
+/// #[no_timeout]
+/// fn whole_dataset_migration() {
+///     // ... minutes of work
+/// }
+/// ```
+///
+/// Sync tests with the default timeout run like the `mode = watchdog` ones (so they can
+/// take not `Send` values): when the timeout expires the test is cancelled and, if it
+/// doesn't stop, it fails and its thread is leaked. So a hung test fails without block
+/// your CI forever or abort the other tests.
+///
+/// ### Timeout report
+///
+/// The failure message of a timed out test is a report like the following one:
+///
+/// ```text
+/// Timeout 50ms expired
+///     test:     my_crate::tests::download::case_3_slow_server
+///     timeout:  50ms
+///     elapsed:  51.044159ms
+///     thread:   tests::download::case_3_slow_server
+///     state:    stopped at checkpoint()
+///     arguments:
+///         url = "http://localhost:8080"
+/// stack backtrace:
+///    0: rstest::timeout::checkpoint
+/// ...
+/// ```
+///
+/// Just the `#[trace]`d arguments are reported. The `state` tells if the sync test
+/// stopped at a `checkpoint()` (the backtrace shows where), completed after the
+/// timeout or is still running because it never reached a `checkpoint()`; async
+/// tests are just dropped.
+///
+/// ## Retry flaky tests
+///
+/// `#[retries(n)]` runs a failing test again up to `n` more times: the test fails
+/// only if every attempt fails (panics or returns an `Err`) and it reports the last
+/// failure. Every attempt resolves all fixtures from scratch and prints its own
+/// `TEST START` banner; async tests retry the whole future.
+///
+/// ```rust,ignore
+/// # use rstest::*;
+/// #[rstest]
+/// #[retries(2)]
+/// fn connect_to_the_test_server(server: TestServer) {
+///     assert!(server.connect().is_ok());
+/// }
+/// ```
+///
+/// Like `#[timeout]`, you can use `#[retries(n)]` on a single case: the case's one
+/// overrides the test's one.
+///
+/// ```rust,ignore
+/// # use rstest::*;
+/// #[rstest]
+/// #[case::local("127.0.0.1")]
+/// #[retries(3)]
+/// #[case::remote("test.example.com")]
+/// fn ping(#[case] host: &str) {
+///     assert!(ping(host));
+/// }
+/// ```
+///
 /// ## Inject Test Attribute
 ///
 /// If you would like to use another `test` attribute for your test you can simply
@@ -850,6 +1318,143 @@ pub fn fixture(
 /// }
 /// ```
 ///
+/// ### Override values in a case
+///
+/// When cases and values lists are used together every case is combined with all
+/// values. If a case makes sense just for some values you can override the values
+/// list of an argument in the case by appending `arg = [v1, ..., vn]` to the case
+/// arguments:
+///
+/// ```rust,ignore
+/// # use rstest::*;
+/// # #[derive(Clone, Copy)] enum Mode { A, B }
+/// # fn run(n: u32, mode: Mode) {}
+/// #[rstest]
+/// #[case(1)]
+/// #[case(2)]
+/// #[case(3, mode = [Mode::A])]
+/// fn test(#[case] n: u32, #[values(Mode::A, Mode::B)] mode: Mode) {
+///     run(n, mode)
+/// }
+/// ```
+///
+/// Here `case_3` runs just with `Mode::A`. The overridden values are named by their
+/// index in the override list (`test::case_3::mode_1`).
+///
+/// ### Matrix modes
+///
+/// By default `rstest` generates a test for every combination of the values, but
+/// the tests grow fast with the number of arguments: you can use `#[matrix(mode)]`
+/// to change how the values lists are combined.
+///
+/// - `#[matrix(product)]`: every combination of the values (the default)
+/// - `#[matrix(zip)]`: the i-th test takes the i-th value of every list. All lists
+///   should have the same length, otherwise you get a compile error
+/// - `#[matrix(pairwise)]`: every pair of values of two different arguments is tested
+///   at least once. The combinations are computed by a deterministic algorithm, so the
+///   tests names don't change if the lists don't
+///
+/// ```rust,ignore
+/// # use rstest::*;
+/// # fn connect(host: &str, port: u16, tls: bool) -> bool { true }
+/// #[rstest]
+/// #[matrix(zip)]
+/// fn endpoint(
+///     #[values("localhost", "example.com")] host: &str,
+///     #[values(8080, 443)] port: u16,
+///     #[values(false, true)] tls: bool,
+/// ) {
+///     assert!(connect(host, port, tls))
+/// }
+/// ```
+///
+/// This generates just two tests: `endpoint::host_1::port_1::tls_1` and
+/// `endpoint::host_2::port_2::tls_2`. The modules names are the same of the
+/// cartesian product ones. In the compact syntax you can use `::matrix(zip)` as an
+/// attribute: `#[rstest(a => [1, 2], b => [3, 4] ::matrix(zip))]`.
+///
+/// ### Exclude combinations
+///
+/// Sometimes a combination of values is impossible: rather than guard the test body
+/// with an `if` (and report the impossible tests as passed) you can remove them from
+/// the matrix with `#[exclude(arg_a = value, arg_b = value, ...)]`. A test is excluded
+/// when all its arguments take the given values; you can use more `#[exclude]`
+/// attributes.
+///
+/// ```rust,ignore
+/// # use rstest::*;
+/// # #[derive(Clone, Copy)] enum Codec { Raw, Zstd }
+/// # fn roundtrip(codec: Codec, level: u32) {}
+/// #[rstest]
+/// #[exclude(codec = Codec::Raw, level = high)]
+/// fn compress(
+///     #[values(Codec::Raw, Codec::Zstd)] codec: Codec,
+///     #[values(0, high => 19)] level: u32,
+/// ) {
+///     roundtrip(codec, level)
+/// }
+/// ```
+///
+/// The excluded values can be written by the same expression used in the values list
+/// or by their label. The excluded tests are not generated at all, so the names of
+/// the other tests don't change. If the argument is not a values list or the value is
+/// not in its list you get a compile error.
+///
+/// ## Property based arguments
+///
+/// An argument annotated by `#[strategy(expr)]` is generated randomly by the
+/// `expr` strategy (see [`rstest::strategy`](https://docs.rs/rstest/latest/rstest/strategy/index.html)):
+/// the test is executed `100` times, the other arguments are resolved again for
+/// every run and can be fixtures, cases or values lists.
+///
+/// ```rust,ignore
+/// # use rstest::*;
+/// use rstest::strategy::{any, vec};
+///
+/// #[rstest]
+/// fn store_keeps_all_items(
+///     mut store: Store,
+///     #[strategy(vec(any::<u32>(), 0..50))] items: Vec<u32>,
+///     #[strategy(1..10usize)] chunk: usize,
+/// ) {
+///     store.save_in_chunks(&items, chunk);
+///     assert_eq!(items, store.load());
+/// }
+/// ```
+///
+/// Integer ranges, `any::<T>()`, `Just(value)`, `vec(strategy, len)` and
+/// `from_fn(|rng| ...)` are provided out of the box, or you can implement
+/// `rstest::strategy::Strategy` trait. When a value makes the test fail it's shrunk to
+/// a minimal failing one that is printed with the seed used to generate it: use
+/// `RSTEST_SEED` environment variable to replay the same values and
+/// `RSTEST_ITERATIONS` to change the number of runs. `#[strategy]` cannot be used in
+/// async tests.
+///
+/// ### Seeded random values
+///
+/// `rstest` provides the `test_rng` fixture: a `TestRng` random numbers generator
+/// seeded by `RSTEST_SEED` environment variable if it's defined or by the test's
+/// name otherwise. It can be injected in tests and fixtures and every fixture of
+/// the same test get the same seed.
+///
+/// ```rust,ignore
+/// use rstest::*;
+///
+/// #[fixture]
+/// fn user(mut test_rng: TestRng) -> User {
+///     User::new(test_rng.gen(18..99u32))
+/// }
+///
+/// #[rstest]
+/// fn adult(user: User) {
+///     assert!(user.is_adult())
+/// }
+/// ```
+///
+/// `TestRng` arguments are always shown in `TEST ARGUMENTS` block (also without
+/// `#[trace]`), so a failing test prints the seed to reproduce it with
+/// `RSTEST_SEED`: use `#[notrace(test_rng)]` to hide them.
+///
 /// ## Trace Input Arguments
 ///
 /// Sometimes can be very helpful to print all test's input arguments. To
@@ -938,7 +1543,7 @@ pub fn fixture(
 /// that and `v1, ..., vl` is a partial list of fixture's arguments
 ///   - `ident => [v1, ..., vl]` where `ident` is one of function arguments and
 /// `v1, ..., vl` is a list of values for ident
-/// - `attribute_j` a test attribute like `trace` or `notrace`
+/// - `attribute_j` a test attribute like `trace`, `notrace` or `auto_names` (derive names from literals)
 ///
 /// ## Fixture Arguments
 ///
@@ -0,0 +1,147 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream, Result},
+    punctuated::Punctuated,
+    Expr, Ident, Token,
+};
+
+use super::vlist::Value;
+
+/// An `#[exclude(arg_a = expr, arg_b = expr)]` attribute: the matrix tests where all
+/// the arguments take the given values are not generated.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct Exclusion {
+    pub(crate) conditions: Vec<Condition>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct Condition {
+    pub(crate) arg: Ident,
+    pub(crate) value: Expr,
+}
+
+impl Condition {
+    /// The value can be given by the same expression used in the values list or by
+    /// its label.
+    pub(crate) fn matches(&self, value: &Value) -> bool {
+        let is_label = match (&self.value, &value.label) {
+            (Expr::Path(path), Some(label)) => path.path.is_ident(label),
+            _ => false,
+        };
+        is_label
+            || self.value.to_token_stream().to_string() == value.expr.to_token_stream().to_string()
+    }
+}
+
+impl Exclusion {
+    /// Is the test excluded? `value_of` returns the value used by the test for the
+    /// given argument.
+    pub(crate) fn matches<'a>(&self, value_of: impl Fn(&Ident) -> Option<&'a Value>) -> bool {
+        self.conditions
+            .iter()
+            .all(|c| value_of(&c.arg).map(|v| c.matches(v)).unwrap_or_default())
+    }
+}
+
+impl Parse for Condition {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let arg = input.parse()?;
+        let _eq: Token![=] = input.parse()?;
+        let value = input.parse()?;
+        Ok(Self { arg, value })
+    }
+}
+
+impl Parse for Exclusion {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let conditions = Punctuated::<Condition, Token![,]>::parse_terminated(input)?
+            .into_iter()
+            .collect::<Vec<_>>();
+        if conditions.is_empty() {
+            return Err(input.error("#[exclude] needs at least one `arg = value` condition"));
+        }
+        for (pos, c) in conditions.iter().enumerate() {
+            if conditions[..pos].iter().any(|prev| prev.arg == c.arg) {
+                return Err(syn::Error::new_spanned(
+                    &c.arg,
+                    format!("Duplicate condition on '{}'", c.arg),
+                ));
+            }
+        }
+        Ok(Self { conditions })
+    }
+}
+
+impl ToTokens for Condition {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let Self { arg, value } = self;
+        tokens.extend(quote! { #arg = #value })
+    }
+}
+
+impl ToTokens for Exclusion {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let conditions = &self.conditions;
+        tokens.extend(quote! { exclude(#(#conditions),*) })
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+
+    fn exclusion(code: &str) -> Exclusion {
+        code.ast()
+    }
+
+    #[test]
+    fn parse_conditions() {
+        let exclusion = exclusion(r#"a = 42, b = "some""#);
+
+        let conditions = exclusion
+            .conditions
+            .iter()
+            .map(|c| c.display_code())
+            .collect::<Vec<_>>();
+
+        assert_eq!(vec!["a = 42", r#"b = "some""#], conditions);
+    }
+
+    #[rstest]
+    #[case::empty("", "at least one")]
+    #[case::duplicate("a = 1, b = 2, a = 3", "Duplicate condition on 'a'")]
+    #[case::not_an_assignment("a", "expected `=`")]
+    fn raise_error(#[case] code: &str, #[case] message: &str) {
+        let error = syn::parse_str::<Exclusion>(code).unwrap_err();
+
+        assert!(
+            error.to_string().contains(message),
+            "'{}' doesn't contain '{}'",
+            error,
+            message
+        );
+    }
+
+    #[rstest]
+    #[case::same_expression("Mode::A", Value::from(expr("Mode::A")), true)]
+    #[case::label("fast", Value::labeled(ident("fast"), expr("Mode::A")), true)]
+    #[case::other_expression("Mode::B", Value::from(expr("Mode::A")), false)]
+    #[case::other_label("slow", Value::labeled(ident("fast"), expr("Mode::A")), false)]
+    fn match_value(#[case] condition: &str, #[case] value: Value, #[case] expected: bool) {
+        let exclusion = exclusion(&format!("a = {}", condition));
+
+        assert_eq!(expected, exclusion.conditions[0].matches(&value));
+    }
+
+    #[test]
+    fn match_just_when_all_conditions_match() {
+        let exclusion = exclusion("a = 1, b = 2");
+        let (one, two) = (Value::from(expr("1")), Value::from(expr("2")));
+
+        assert!(exclusion.matches(|arg| Some(if arg == "a" { &one } else { &two })));
+        assert!(!exclusion.matches(|_| Some(&one)));
+        assert!(!exclusion.matches(|arg| Some(&one).filter(|_| arg == "a")));
+    }
+}
@@ -0,0 +1,423 @@
+//! `#[files(...)]` argument attribute: the glob patterns are resolved at expansion time
+//! relative to `CARGO_MANIFEST_DIR` and expanded in a values list with a value for each
+//! matched file.
+use std::path::{Component, Path, PathBuf};
+
+use proc_macro2::Span;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream, Result},
+    parse_quote,
+    punctuated::Punctuated,
+    Ident, LitStr, Token,
+};
+
+use super::vlist::{Value, ValueList};
+
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct Files {
+    globs: Vec<LitStr>,
+    exclude: Vec<LitStr>,
+    include_dot_files: bool,
+    mode: FilesMode,
+}
+
+/// How the matched files are injected: the `PathBuf` of the file or its content embedded
+/// with `include_str!()` or `include_bytes!()`
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum FilesMode {
+    Path,
+    Str,
+    Bytes,
+}
+
+impl FilesMode {
+    const MODES: &'static [&'static str] = &["path", "str", "bytes"];
+}
+
+impl Parse for FilesMode {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mode: LitStr = input.parse()?;
+        match mode.value().as_str() {
+            "path" => Ok(FilesMode::Path),
+            "str" => Ok(FilesMode::Str),
+            "bytes" => Ok(FilesMode::Bytes),
+            _ => Err(syn::Error::new_spanned(
+                mode,
+                format!(
+                    "Invalid #[files] mode: valid modes are {}",
+                    Self::MODES.join(", ")
+                ),
+            )),
+        }
+    }
+}
+
+enum FilesArg {
+    Glob(LitStr),
+    Exclude(LitStr),
+    IncludeDotFiles,
+    Mode(FilesMode),
+}
+
+impl FilesArg {
+    const EXCLUDE: &'static str = "exclude";
+    const INCLUDE_DOT_FILES: &'static str = "include_dot_files";
+    const MODE: &'static str = "mode";
+}
+
+impl Parse for FilesArg {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(LitStr) {
+            return input.parse().map(FilesArg::Glob);
+        }
+        let ident: Ident = input.parse()?;
+        if ident == Self::EXCLUDE {
+            let _eq: Token![=] = input.parse()?;
+            input.parse().map(FilesArg::Exclude)
+        } else if ident == Self::INCLUDE_DOT_FILES {
+            Ok(FilesArg::IncludeDotFiles)
+        } else if ident == Self::MODE {
+            let _eq: Token![=] = input.parse()?;
+            input.parse().map(FilesArg::Mode)
+        } else {
+            Err(syn::Error::new_spanned(
+                ident,
+                format!(
+                    "Invalid #[files] argument: use glob patterns, {} = \"pattern\", {} or {} = \"mode\"",
+                    Self::EXCLUDE,
+                    Self::INCLUDE_DOT_FILES,
+                    Self::MODE
+                ),
+            ))
+        }
+    }
+}
+
+impl Parse for Files {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut files = Files {
+            globs: vec![],
+            exclude: vec![],
+            include_dot_files: false,
+            mode: FilesMode::Path,
+        };
+        for arg in Punctuated::<FilesArg, Token![,]>::parse_terminated(input)? {
+            match arg {
+                FilesArg::Glob(glob) => files.globs.push(glob),
+                FilesArg::Exclude(pattern) => files.exclude.push(pattern),
+                FilesArg::IncludeDotFiles => files.include_dot_files = true,
+                FilesArg::Mode(mode) => files.mode = mode,
+            }
+        }
+        if files.globs.is_empty() {
+            return Err(input.error("#[files] needs at least a glob pattern"));
+        }
+        if let Some(absolute) = files
+            .globs
+            .iter()
+            .find(|g| Path::new(&g.value()).is_absolute())
+        {
+            return Err(syn::Error::new_spanned(
+                absolute,
+                "#[files] glob pattern should be relative to CARGO_MANIFEST_DIR",
+            ));
+        }
+        Ok(files)
+    }
+}
+
+/// The environment variable that `rstest::build::track_files()` sets in the build script
+/// with the hash of the tracked directories: reading it makes cargo recompile the tests,
+/// and so expand again the globs, every time a file is added or removed.
+const FILES_HASH_ENV: &str = "RSTEST_FILES_HASH";
+
+impl Files {
+    /// Resolve the files relative to `CARGO_MANIFEST_DIR` and build the values list for `arg`
+    pub(crate) fn value_list(&self, arg: &Ident) -> Result<ValueList> {
+        let base_dir = std::env::var("CARGO_MANIFEST_DIR").map_err(|_| {
+            syn::Error::new(
+                self.span(),
+                "Cannot resolve #[files] patterns: CARGO_MANIFEST_DIR is not defined",
+            )
+        })?;
+        Ok(ValueList {
+            arg: arg.clone(),
+            values: self.values(Path::new(&base_dir))?,
+        })
+    }
+
+    fn span(&self) -> Span {
+        self.globs[0].span()
+    }
+
+    fn match_options(&self) -> glob::MatchOptions {
+        glob::MatchOptions {
+            require_literal_leading_dot: !self.include_dot_files,
+            ..Default::default()
+        }
+    }
+
+    /// The sorted paths, relative to `base_dir`, of all matched files
+    fn paths(&self, base_dir: &Path) -> Result<Vec<PathBuf>> {
+        let options = self.match_options();
+        let exclude = self
+            .exclude
+            .iter()
+            .map(|e| glob::Pattern::new(&e.value()).map_err(|err| syn::Error::new_spanned(e, err)))
+            .collect::<Result<Vec<_>>>()?;
+        let base = glob::Pattern::escape(&base_dir.to_string_lossy());
+        let mut paths = vec![];
+        for g in &self.globs {
+            let pattern = format!("{}/{}", base, g.value());
+            let entries = glob::glob_with(&pattern, options)
+                .map_err(|err| syn::Error::new_spanned(g, err))?;
+            for entry in entries {
+                let path = entry.map_err(|err| syn::Error::new_spanned(g, err))?;
+                if !path.is_file() {
+                    continue;
+                }
+                let relative = path.strip_prefix(base_dir).unwrap().to_owned();
+                if !exclude
+                    .iter()
+                    .any(|e| e.matches_path_with(&relative, options))
+                {
+                    paths.push(relative);
+                }
+            }
+        }
+        paths.sort();
+        paths.dedup();
+        if paths.is_empty() {
+            return Err(syn::Error::new(
+                self.span(),
+                "No file matches #[files] glob patterns",
+            ));
+        }
+        Ok(paths)
+    }
+
+    fn values(&self, base_dir: &Path) -> Result<Vec<Value>> {
+        Ok(self
+            .paths(base_dir)?
+            .into_iter()
+            .map(|relative| {
+                let relative_str = unix_path(&relative);
+                let path = quote! { concat!(env!("CARGO_MANIFEST_DIR"), "/", #relative_str) };
+                let value = match self.mode {
+                    FilesMode::Path => quote! {
+                        const _: &[u8] = include_bytes!(#path);
+                        std::path::PathBuf::from(#path)
+                    },
+                    FilesMode::Str => quote! { include_str!(#path) },
+                    FilesMode::Bytes => quote! { include_bytes!(#path) },
+                };
+                Value::new(
+                    parse_quote! {
+                        {
+                            const _: Option<&str> = option_env!(#FILES_HASH_ENV);
+                            #value
+                        }
+                    },
+                    Some(sanitize_file_name(&relative)),
+                )
+            })
+            .collect())
+    }
+}
+
+fn unix_path(path: &Path) -> String {
+    path.components()
+        .filter_map(|c| match c {
+            Component::Normal(c) => Some(c.to_string_lossy()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Test name suffix: the lowercase file name where all chars that cannot be in an
+/// identifier are replaced by `_`
+fn sanitize_file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy())
+        .unwrap_or_default()
+        .chars()
+        .map(|c| match c {
+            c if c.is_ascii_alphanumeric() => c.to_ascii_lowercase(),
+            _ => '_',
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use rstest_test::assert_in;
+
+    fn files(attr: &str) -> Files {
+        syn::parse_str(attr).unwrap()
+    }
+
+    /// Create a clean directory with the given (empty) files
+    fn base_dir(name: &str, files: &[&str]) -> PathBuf {
+        let base = std::env::temp_dir().join("rstest_macros_files").join(name);
+        let _ = std::fs::remove_dir_all(&base);
+        for f in files {
+            let path = base.join(f);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "").unwrap();
+        }
+        base
+    }
+
+    fn relative_paths(files: &Files, base: &Path) -> Vec<String> {
+        files
+            .paths(base)
+            .unwrap()
+            .iter()
+            .map(|p| unix_path(p))
+            .collect()
+    }
+
+    #[test]
+    fn parse_globs_and_options() {
+        let f =
+            files(r#""data/*.json", "other/**/*.txt", exclude = "**/skip*", include_dot_files"#);
+
+        assert_eq!(2, f.globs.len());
+        assert_eq!(1, f.exclude.len());
+        assert!(f.include_dot_files);
+        assert_eq!(FilesMode::Path, f.mode);
+    }
+
+    #[rstest]
+    #[case::path(r#""*.txt", mode = "path""#, FilesMode::Path)]
+    #[case::str(r#""*.txt", mode = "str""#, FilesMode::Str)]
+    #[case::bytes(r#""*.txt", mode = "bytes""#, FilesMode::Bytes)]
+    fn parse_mode(#[case] attr: &str, #[case] expected: FilesMode) {
+        assert_eq!(expected, files(attr).mode);
+    }
+
+    #[rstest]
+    #[case::no_glob(r#"include_dot_files"#, "at least a glob pattern")]
+    #[case::unknown_argument(r#""*.txt", other"#, "Invalid #[files] argument")]
+    #[case::absolute_path(r#""/data/*.txt""#, "should be relative")]
+    #[case::invalid_mode(r#""*.txt", mode = "text""#, "Invalid #[files] mode")]
+    fn not_parse_invalid_attributes(#[case] attr: &str, #[case] message: &str) {
+        let err = syn::parse_str::<Files>(attr).unwrap_err();
+
+        assert_in!(err.to_string(), message);
+    }
+
+    #[test]
+    fn find_sorted_files_that_match_globs() {
+        let base = base_dir(
+            "find_sorted_files_that_match_globs",
+            &[
+                "data/b.json",
+                "data/a.json",
+                "data/c.txt",
+                "data/sub/d.json",
+            ],
+        );
+
+        let f = files(r#""data/**/*.json""#);
+
+        assert_eq!(
+            vec!["data/a.json", "data/b.json", "data/sub/d.json"],
+            relative_paths(&f, &base)
+        );
+    }
+
+    #[test]
+    fn exclude_files() {
+        let base = base_dir(
+            "exclude_files",
+            &["data/a.json", "data/skip_b.json", "data/sub/skip_c.json"],
+        );
+
+        let f = files(r#""data/**/*.json", exclude = "**/skip_*""#);
+
+        assert_eq!(vec!["data/a.json"], relative_paths(&f, &base));
+    }
+
+    #[test]
+    fn ignore_dot_files_if_not_requested() {
+        let base = base_dir(
+            "ignore_dot_files_if_not_requested",
+            &["data/a.json", "data/.hidden.json", "data/.dir/b.json"],
+        );
+
+        assert_eq!(
+            vec!["data/a.json"],
+            relative_paths(&files(r#""data/**/*.json""#), &base)
+        );
+        assert_eq!(
+            vec!["data/.dir/b.json", "data/.hidden.json", "data/a.json"],
+            relative_paths(&files(r#""data/**/*.json", include_dot_files"#), &base)
+        );
+    }
+
+    #[test]
+    fn return_error_if_no_file_matches() {
+        let base = base_dir("return_error_if_no_file_matches", &["data/a.json"]);
+
+        let err = files(r#""data/*.txt""#).paths(&base).unwrap_err();
+
+        assert_in!(err.to_string(), "No file matches");
+    }
+
+    #[test]
+    fn build_values_relative_to_manifest_dir_and_named_as_files() {
+        let base = base_dir(
+            "build_values_relative_to_manifest_dir_and_named_as_files",
+            &["data/First-File.json"],
+        );
+
+        let values = files(r#""data/*.json""#).values(&base).unwrap();
+
+        assert_eq!(
+            vec![Value::new(
+                expr(
+                    r#"{
+                        const _: Option<&str> = option_env!("RSTEST_FILES_HASH");
+                        const _: &[u8] = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/", "data/First-File.json"));
+                        std::path::PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/", "data/First-File.json"))
+                    }"#
+                ),
+                Some("first_file_json".to_string())
+            )],
+            values
+        );
+    }
+
+    #[rstest]
+    #[case::str(
+        "str",
+        r#"{
+            const _: Option<&str> = option_env!("RSTEST_FILES_HASH");
+            include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/", "data/a.txt"))
+        }"#
+    )]
+    #[case::bytes(
+        "bytes",
+        r#"{
+            const _: Option<&str> = option_env!("RSTEST_FILES_HASH");
+            include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/", "data/a.txt"))
+        }"#
+    )]
+    fn embed_file_content(#[case] mode: &str, #[case] expected: &str) {
+        let base = base_dir(&format!("embed_file_content_{}", mode), &["data/a.txt"]);
+
+        let values = files(&format!(r#""data/*.txt", mode = "{}""#, mode))
+            .values(&base)
+            .unwrap();
+
+        assert_eq!(
+            vec![Value::new(expr(expected), Some("a_txt".to_string()))],
+            values
+        );
+    }
+}
@@ -13,8 +13,8 @@ use super::{
 };
 use crate::{
     error::ErrorsVec,
-    parse::extract_once,
-    refident::{MaybeIdent, RefIdent},
+    parse::{extract_once, extract_scope, extract_teardown},
+    refident::RefIdent,
     utils::attr_is,
 };
 use crate::{parse::Attribute, utils::attr_in};
@@ -59,13 +59,17 @@ impl ExtendWithFunctionAttrs for FixtureInfo {
             defaults,
             default_return_type,
             partials_return_type,
-            once
+            once,
+            teardown,
+            scope
         ) = merge_errors!(
             extract_fixtures(item_fn),
             extract_defaults(item_fn),
             extract_default_return_type(item_fn),
             extract_partials_return_type(item_fn),
-            extract_once(item_fn)
+            extract_once(item_fn),
+            extract_teardown(item_fn),
+            extract_scope(item_fn)
         )?;
         self.data.items.extend(
             fixtures
@@ -79,8 +83,14 @@ impl ExtendWithFunctionAttrs for FixtureInfo {
         for (id, return_type) in partials_return_type {
             self.attributes.set_partial_return_type(id, return_type);
         }
-        if let Some(ident) = once {
-            self.attributes.set_once(ident)
+        if let Some(once) = once {
+            self.attributes.append(once)
+        };
+        if let Some(teardown) = teardown {
+            self.attributes.set_teardown(teardown)
+        };
+        if let Some(scope) = scope {
+            self.attributes.set_scope(scope)
         };
         Ok(())
     }
@@ -271,6 +281,15 @@ wrap_attributes!(FixtureModifiers);
 impl FixtureModifiers {
     pub(crate) const DEFAULT_RET_ATTR: &'static str = "default";
     pub(crate) const PARTIAL_RET_ATTR: &'static str = "partial_";
+    pub(crate) const TEARDOWN_ATTR: &'static str = "teardown";
+    pub(crate) const SCOPE_ATTR: &'static str = "scope";
+    pub(crate) const MODULE_SCOPE: &'static str = "module";
+    pub(crate) const SCOPES: &'static [&'static str] = &[Self::MODULE_SCOPE];
+    pub(crate) const ONCE_ATTR: &'static str = "once";
+    pub(crate) const ONCE_DROP_ARG: &'static str = "drop";
+    pub(crate) const ONCE_RESET_ARG: &'static str = "reset";
+    pub(crate) const ONCE_ARGS: &'static [&'static str] =
+        &[Self::ONCE_DROP_ARG, Self::ONCE_RESET_ARG];
 
     pub(crate) fn extract_default_type(&self) -> Option<syn::ReturnType> {
         self.extract_type(Self::DEFAULT_RET_ATTR)
@@ -294,20 +313,68 @@ impl FixtureModifiers {
         ))
     }
 
-    pub(crate) fn set_once(&mut self, once: syn::Ident) {
-        self.inner.attributes.push(Attribute::Attr(once))
+    pub(crate) fn get_once(&self) -> Option<&Ident> {
+        self.iter().find_map(|a| match a {
+            Attribute::Attr(once) | Attribute::Tagged(once, _) if once == Self::ONCE_ATTR => {
+                Some(once)
+            }
+            _ => None,
+        })
     }
 
-    pub(crate) fn get_once(&self) -> Option<&Ident> {
-        self.iter()
-            .find(|&a| a == &Attribute::Attr(format_ident!("once")))
-            .and_then(|a| a.maybe_ident())
+    fn has_once_arg(&self, name: &str) -> bool {
+        self.iter().any(|a| {
+            matches!(a, Attribute::Tagged(once, args)
+                if once == Self::ONCE_ATTR && args.iter().any(|arg| arg == name))
+        })
+    }
+
+    pub(crate) fn is_once_drop(&self) -> bool {
+        self.has_once_arg(Self::ONCE_DROP_ARG)
+    }
+
+    pub(crate) fn is_once_reset(&self) -> bool {
+        self.has_once_arg(Self::ONCE_RESET_ARG)
     }
 
     pub(crate) fn is_once(&self) -> bool {
         self.get_once().is_some()
     }
 
+    pub(crate) fn set_teardown(&mut self, teardown: syn::Expr) {
+        self.inner.attributes.push(Attribute::Expr(
+            format_ident!("{}", Self::TEARDOWN_ATTR),
+            Box::new(teardown),
+        ))
+    }
+
+    pub(crate) fn set_scope(&mut self, scope: Ident) {
+        self.inner.attributes.push(Attribute::Tagged(
+            format_ident!("{}", Self::SCOPE_ATTR),
+            vec![scope],
+        ))
+    }
+
+    pub(crate) fn get_scope(&self) -> Option<&Ident> {
+        self.iter().find_map(|m| match m {
+            Attribute::Tagged(name, scopes) if name == Self::SCOPE_ATTR => scopes.first(),
+            _ => None,
+        })
+    }
+
+    pub(crate) fn is_module_scope(&self) -> bool {
+        self.get_scope()
+            .map(|scope| scope == Self::MODULE_SCOPE)
+            .unwrap_or(false)
+    }
+
+    pub(crate) fn get_teardown(&self) -> Option<&syn::Expr> {
+        self.iter().find_map(|m| match m {
+            Attribute::Expr(name, e) if name == Self::TEARDOWN_ATTR => Some(e.as_ref()),
+            _ => None,
+        })
+    }
+
     fn extract_type(&self, attr_name: &str) -> Option<syn::ReturnType> {
         self.iter()
             .filter_map(|m| match m {
@@ -573,6 +640,58 @@ mod extend {
             info.extend_with_function_attrs(&mut item_fn).unwrap();
 
             assert!(info.attributes.is_once());
+            assert!(!info.attributes.is_once_drop());
+        }
+
+        #[test]
+        fn find_once_drop_attribute() {
+            let mut item_fn: ItemFn = r#"
+                #[once(drop)]
+                fn my_fix() -> u32 { 42 }
+            "#
+            .ast();
+
+            let mut info = FixtureInfo::default();
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            assert!(info.attributes.is_once());
+            assert!(info.attributes.is_once_drop());
+            assert!(item_fn.attrs.is_empty());
+        }
+
+        #[test]
+        fn find_once_reset_attribute() {
+            let mut item_fn: ItemFn = r#"
+                #[once(drop, reset)]
+                fn my_fix() -> u32 { 42 }
+            "#
+            .ast();
+
+            let mut info = FixtureInfo::default();
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            assert!(info.attributes.is_once());
+            assert!(info.attributes.is_once_drop());
+            assert!(info.attributes.is_once_reset());
+        }
+
+        #[test]
+        fn find_scope_attribute() {
+            let mut item_fn: ItemFn = r#"
+                #[simple]
+                #[scope(module)]
+                fn my_fix() -> u32 { 42 }
+            "#
+            .ast();
+
+            let mut info = FixtureInfo::default();
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            assert!(info.attributes.is_module_scope());
+            assert_eq!(attrs("#[simple]"), item_fn.attrs);
         }
 
         #[test]
@@ -589,6 +708,27 @@ mod extend {
             assert!(!info.attributes.is_once());
         }
 
+        #[test]
+        fn find_teardown_attribute() {
+            let mut item_fn: ItemFn = r#"
+                #[simple]
+                #[teardown(|v| move || drop(v))]
+                #[last::more]
+                fn my_fix() -> u32 {}
+            "#
+            .ast();
+
+            let mut info = FixtureInfo::default();
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            assert_eq!(
+                Some(&expr("|v| move || drop(v)")),
+                info.attributes.get_teardown()
+            );
+            assert_eq!(attrs("#[simple]#[last::more]"), item_fn.attrs);
+        }
+
         mod raise_error {
             use super::{assert_eq, *};
             use rstest_test::assert_in;
@@ -671,6 +811,59 @@ mod extend {
                 );
             }
 
+            #[test]
+            fn if_once_has_an_invalid_argument() {
+                let mut item_fn: ItemFn = r#"
+                    #[once(drop, other)]
+                    fn my_fix() -> u32 {}
+                    "#
+                .ast();
+
+                let mut info = FixtureInfo::default();
+
+                let error = info.extend_with_function_attrs(&mut item_fn).unwrap_err();
+
+                assert_in!(
+                    format!("{:?}", error).to_lowercase(),
+                    "invalid #[once] argument"
+                );
+            }
+
+            #[rstest]
+            #[case::unknown_scope("#[scope(process)]", "invalid scope")]
+            #[case::more_than_once(
+                "#[scope(module)] #[scope(module)]",
+                "cannot use #[scope] more than once"
+            )]
+            fn if_scope_is_not_valid(#[case] scope: &str, #[case] message: &str) {
+                let mut item_fn: ItemFn = format!("{} fn my_fix() -> u32 {{ 42 }}", scope).ast();
+
+                let mut info = FixtureInfo::default();
+
+                let error = info.extend_with_function_attrs(&mut item_fn).unwrap_err();
+
+                assert_in!(format!("{:?}", error).to_lowercase(), message);
+            }
+
+            #[test]
+            fn if_teardown_is_defined_more_than_once() {
+                let mut item_fn: ItemFn = r#"
+                    #[teardown(|_| || ())]
+                    #[teardown(|_| || ())]
+                    fn my_fix() -> u32 {}
+                    "#
+                .ast();
+
+                let mut info = FixtureInfo::default();
+
+                let error = info.extend_with_function_attrs(&mut item_fn).unwrap_err();
+
+                assert_in!(
+                    format!("{:?}", error).to_lowercase(),
+                    "cannot use #[teardown] more than once"
+                );
+            }
+
             #[test]
             fn if_default_is_defined_more_than_once() {
                 let mut item_fn: ItemFn = r#"
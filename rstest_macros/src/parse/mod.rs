@@ -19,17 +19,28 @@ use fixture::{
 use quote::ToTokens;
 use testcase::TestCase;
 
-use self::{expressions::Expressions, vlist::ValueList};
+use self::{
+    exclude::Exclusion,
+    expressions::Expressions,
+    files::Files,
+    table::Table,
+    timeout::{Timeout, TimeoutMode},
+    vlist::{ValueList, Values, ValuesFrom},
+};
 
 // To use the macros this should be the first one module
 #[macro_use]
 pub(crate) mod macros;
 
+pub(crate) mod exclude;
 pub(crate) mod expressions;
+pub(crate) mod files;
 pub(crate) mod fixture;
 pub(crate) mod future;
 pub(crate) mod rstest;
+pub(crate) mod table;
 pub(crate) mod testcase;
+pub(crate) mod timeout;
 pub(crate) mod vlist;
 
 pub(crate) trait ExtendWithFunctionAttrs {
@@ -58,6 +69,7 @@ pub(crate) enum Attribute {
     Attr(Ident),
     Tagged(Ident, Vec<Ident>),
     Type(Ident, Box<syn::Type>),
+    Expr(Ident, Box<syn::Expr>),
 }
 
 impl Parse for Attribute {
@@ -224,12 +236,25 @@ pub(crate) fn extract_partials_return_type(
     partials_type_extractor.take()
 }
 
-pub(crate) fn extract_once(item_fn: &mut ItemFn) -> Result<Option<Ident>, ErrorsVec> {
+pub(crate) fn extract_once(item_fn: &mut ItemFn) -> Result<Option<Attribute>, ErrorsVec> {
     let mut extractor = IsOnceAttributeFunctionExtractor::default();
     extractor.visit_item_fn_mut(item_fn);
     extractor.take()
 }
 
+pub(crate) fn extract_teardown(item_fn: &mut ItemFn) -> Result<Option<syn::Expr>, ErrorsVec> {
+    let mut extractor = TearDownFunctionExtractor::default();
+    extractor.visit_item_fn_mut(item_fn);
+    extractor.take()
+}
+
+/// Extract fixture's scope from `#[scope(...)]` function attribute
+pub(crate) fn extract_scope(item_fn: &mut ItemFn) -> Result<Option<Ident>, ErrorsVec> {
+    let mut extractor = ScopeFunctionExtractor::default();
+    extractor.visit_item_fn_mut(item_fn);
+    extractor.take()
+}
+
 fn extract_argument_attrs<'a, B: 'a + std::fmt::Debug>(
     node: &mut FnArg,
     is_valid_attr: fn(&syn::Attribute) -> bool,
@@ -360,13 +385,41 @@ impl VisitMut for PartialsTypeFunctionExtractor {
 }
 
 /// Simple struct used to visit function attributes and extract once
-/// type
-struct IsOnceAttributeFunctionExtractor(Result<Option<Ident>, ErrorsVec>);
+/// type and its arguments
+struct IsOnceAttributeFunctionExtractor(Result<Option<Attribute>, ErrorsVec>);
 
 impl IsOnceAttributeFunctionExtractor {
-    fn take(self) -> Result<Option<Ident>, ErrorsVec> {
+    fn take(self) -> Result<Option<Attribute>, ErrorsVec> {
         self.0
     }
+
+    fn parse_once(attr: &syn::Attribute) -> Result<Attribute, ErrorsVec> {
+        let once = attr.path.get_ident().cloned().unwrap();
+        if attr.tokens.is_empty() {
+            return Ok(Attribute::Attr(once));
+        }
+        let args = attr
+            .parse_args_with(Punctuated::<Ident, Token![,]>::parse_terminated)
+            .map_err(ErrorsVec::from)?;
+        let errors = args
+            .iter()
+            .filter(|&arg| !FixtureModifiers::ONCE_ARGS.iter().any(|a| arg == a))
+            .map(|arg| {
+                syn::Error::new_spanned(
+                    arg,
+                    format!(
+                        "Invalid #[once] argument: valid arguments are {}",
+                        FixtureModifiers::ONCE_ARGS.join(", ")
+                    ),
+                )
+            })
+            .collect::<Vec<_>>();
+        if errors.is_empty() {
+            Ok(Attribute::Tagged(once, args.into_iter().collect()))
+        } else {
+            Err(errors.into())
+        }
+    }
 }
 
 impl Default for IsOnceAttributeFunctionExtractor {
@@ -383,7 +436,7 @@ impl VisitMut for IsOnceAttributeFunctionExtractor {
 
         node.attrs = remain;
         self.0 = match onces.len() {
-            1 => Ok(onces[0].path.get_ident().cloned()),
+            1 => Self::parse_once(&onces[0]).map(Some),
             0 => Ok(None),
             _ => Err(onces
                 .into_iter()
@@ -396,6 +449,208 @@ impl VisitMut for IsOnceAttributeFunctionExtractor {
     }
 }
 
+/// Simple struct used to visit function attributes and extract teardown
+/// expression
+struct TearDownFunctionExtractor(Result<Option<syn::Expr>, ErrorsVec>);
+
+impl TearDownFunctionExtractor {
+    fn take(self) -> Result<Option<syn::Expr>, ErrorsVec> {
+        self.0
+    }
+}
+
+impl Default for TearDownFunctionExtractor {
+    fn default() -> Self {
+        Self(Ok(None))
+    }
+}
+
+impl VisitMut for TearDownFunctionExtractor {
+    fn visit_item_fn_mut(&mut self, node: &mut ItemFn) {
+        let attrs = std::mem::take(&mut node.attrs);
+        let (teardowns, remain): (Vec<_>, Vec<_>) = attrs
+            .into_iter()
+            .partition(|attr| attr_is(attr, FixtureModifiers::TEARDOWN_ATTR));
+
+        node.attrs = remain;
+        let mut teardowns = teardowns.into_iter();
+        let mut data = None;
+        let mut errors = ErrorsVec::default();
+        match teardowns.next().map(|t| t.parse_args::<syn::Expr>()) {
+            Some(Ok(e)) => data = Some(e),
+            Some(Err(e)) => errors.push(e),
+            None => {}
+        };
+        errors.extend(
+            teardowns
+                .map(|a| syn::Error::new_spanned(a, "You cannot use #[teardown] more than once")),
+        );
+        self.0 = if !errors.is_empty() {
+            Err(errors)
+        } else {
+            Ok(data)
+        };
+
+        syn::visit_mut::visit_item_fn_mut(self, node);
+    }
+}
+
+/// Simple struct used to visit function attributes and extract the fixture's scope
+struct ScopeFunctionExtractor(Result<Option<Ident>, ErrorsVec>);
+
+impl ScopeFunctionExtractor {
+    fn take(self) -> Result<Option<Ident>, ErrorsVec> {
+        self.0
+    }
+}
+
+impl Default for ScopeFunctionExtractor {
+    fn default() -> Self {
+        Self(Ok(None))
+    }
+}
+
+impl VisitMut for ScopeFunctionExtractor {
+    fn visit_item_fn_mut(&mut self, node: &mut ItemFn) {
+        let attrs = std::mem::take(&mut node.attrs);
+        let (scopes, remain): (Vec<_>, Vec<_>) = attrs
+            .into_iter()
+            .partition(|attr| attr_is(attr, FixtureModifiers::SCOPE_ATTR));
+
+        node.attrs = remain;
+        let mut scopes = scopes.into_iter();
+        let mut data = None;
+        let mut errors = ErrorsVec::default();
+        match scopes.next().map(|s| s.parse_args::<Ident>()) {
+            Some(Ok(scope)) if FixtureModifiers::SCOPES.iter().any(|s| scope == s) => {
+                data = Some(scope)
+            }
+            Some(Ok(scope)) => errors.push(syn::Error::new_spanned(
+                scope,
+                format!(
+                    "Invalid scope: valid scopes are {}",
+                    FixtureModifiers::SCOPES.join(", ")
+                ),
+            )),
+            Some(Err(e)) => errors.push(e),
+            None => {}
+        };
+        errors.extend(
+            scopes.map(|a| syn::Error::new_spanned(a, "You cannot use #[scope] more than once")),
+        );
+        self.0 = if !errors.is_empty() {
+            Err(errors)
+        } else {
+            Ok(data)
+        };
+
+        syn::visit_mut::visit_item_fn_mut(self, node);
+    }
+}
+
+/// Simple struct used to visit function attributes and extract the `#[matrix(mode)]`
+/// modes and eventualy parsing errors
+#[derive(Default)]
+struct MatrixModeFunctionExtractor(Vec<Ident>, Vec<syn::Error>);
+
+impl VisitMut for MatrixModeFunctionExtractor {
+    fn visit_item_fn_mut(&mut self, node: &mut ItemFn) {
+        let attrs = std::mem::take(&mut node.attrs);
+        let (modes, remain): (Vec<_>, Vec<_>) = attrs
+            .into_iter()
+            .partition(|attr| attr_is(attr, rstest::RsTestAttributes::MATRIX_ATTR));
+
+        node.attrs = remain;
+        for mode in modes {
+            match mode.parse_args::<Ident>() {
+                Ok(mode) => self.0.push(mode),
+                Err(err) => self.1.push(err),
+            }
+        }
+
+        syn::visit_mut::visit_item_fn_mut(self, node);
+    }
+}
+
+pub(crate) fn extract_matrix_modes(item_fn: &mut ItemFn) -> Result<Vec<Ident>, ErrorsVec> {
+    let mut extractor = MatrixModeFunctionExtractor::default();
+    extractor.visit_item_fn_mut(item_fn);
+
+    if extractor.1.is_empty() {
+        Ok(extractor.0)
+    } else {
+        Err(extractor.1.into())
+    }
+}
+
+/// Simple struct used to visit function attributes and extract the `#[exclude(...)]`
+/// matrix exclusions and eventualy parsing errors
+#[derive(Default)]
+struct ExcludeFunctionExtractor(Vec<Exclusion>, Vec<syn::Error>);
+
+impl VisitMut for ExcludeFunctionExtractor {
+    fn visit_item_fn_mut(&mut self, node: &mut ItemFn) {
+        let attrs = std::mem::take(&mut node.attrs);
+        let (exclusions, remain): (Vec<_>, Vec<_>) =
+            attrs.into_iter().partition(|attr| attr_is(attr, "exclude"));
+
+        node.attrs = remain;
+        for exclusion in exclusions {
+            match exclusion.parse_args::<Exclusion>() {
+                Ok(exclusion) => self.0.push(exclusion),
+                Err(err) => self.1.push(err),
+            }
+        }
+
+        syn::visit_mut::visit_item_fn_mut(self, node);
+    }
+}
+
+pub(crate) fn extract_exclusions(item_fn: &mut ItemFn) -> Result<Vec<Exclusion>, ErrorsVec> {
+    let mut extractor = ExcludeFunctionExtractor::default();
+    extractor.visit_item_fn_mut(item_fn);
+
+    if extractor.1.is_empty() {
+        Ok(extractor.0)
+    } else {
+        Err(extractor.1.into())
+    }
+}
+
+/// Simple struct used to visit function attributes and extract the `#[table("path")]`
+/// tables and eventualy parsing errors
+#[derive(Default)]
+struct TableFunctionExtractor(Vec<Table>, Vec<syn::Error>);
+
+impl VisitMut for TableFunctionExtractor {
+    fn visit_item_fn_mut(&mut self, node: &mut ItemFn) {
+        let attrs = std::mem::take(&mut node.attrs);
+        let (tables, remain): (Vec<_>, Vec<_>) =
+            attrs.into_iter().partition(|attr| attr_is(attr, "table"));
+
+        node.attrs = remain;
+        for table in tables {
+            match table.parse_args::<Table>() {
+                Ok(table) => self.0.push(table),
+                Err(err) => self.1.push(err),
+            }
+        }
+
+        syn::visit_mut::visit_item_fn_mut(self, node);
+    }
+}
+
+pub(crate) fn extract_tables(item_fn: &mut ItemFn) -> Result<Vec<Table>, ErrorsVec> {
+    let mut extractor = TableFunctionExtractor::default();
+    extractor.visit_item_fn_mut(item_fn);
+
+    if extractor.1.is_empty() {
+        Ok(extractor.0)
+    } else {
+        Err(extractor.1.into())
+    }
+}
+
 /// Simple struct used to visit function attributes and extract case arguments and
 /// eventualy parsing errors
 #[derive(Default)]
@@ -444,6 +699,8 @@ impl VisitMut for CasesFunctionExtractor {
                             args: expressions.into(),
                             attrs: std::mem::take(&mut attrs_buffer),
                             description,
+                            text_description: None,
+                            overrides: vec![],
                         });
                     }
                     Err(err) => self.1.push(err),
@@ -479,7 +736,7 @@ impl VisitMut for ValueListFunctionExtractor {
             node,
             |a| attr_is(a, "values"),
             |a, name| {
-                a.parse_args::<Expressions>().map(|v| ValueList {
+                a.parse_args::<Values>().map(|v| ValueList {
                     arg: name.clone(),
                     values: v.take(),
                 })
@@ -490,6 +747,29 @@ impl VisitMut for ValueListFunctionExtractor {
                 Err(err) => self.1.push(err),
             }
         }
+        for r in extract_argument_attrs(
+            node,
+            |a| attr_is(a, "files"),
+            |a, name| a.parse_args::<Files>().and_then(|f| f.value_list(name)),
+        ) {
+            match r {
+                Ok(vlist) => self.0.push(vlist),
+                Err(err) => self.1.push(err),
+            }
+        }
+        for r in extract_argument_attrs(
+            node,
+            |a| attr_is(a, "values_from"),
+            |a, name| {
+                a.parse_args::<ValuesFrom>()
+                    .and_then(|v| v.value_list(name))
+            },
+        ) {
+            match r {
+                Ok(vlist) => self.0.push(vlist),
+                Err(err) => self.1.push(err),
+            }
+        }
 
         syn::visit_mut::visit_fn_arg_mut(self, node);
     }
@@ -506,6 +786,41 @@ pub(crate) fn extract_value_list(item_fn: &mut ItemFn) -> Result<Vec<ValueList>,
     }
 }
 
+/// Simple struct used to visit function args attributes to extract the
+/// `#[strategy(...)]` generated arguments and eventualy parsing errors
+#[derive(Default)]
+struct StrategyFunctionExtractor(Vec<(Ident, syn::Expr)>, Vec<syn::Error>);
+
+impl VisitMut for StrategyFunctionExtractor {
+    fn visit_fn_arg_mut(&mut self, node: &mut FnArg) {
+        for r in extract_argument_attrs(
+            node,
+            |a| attr_is(a, "strategy"),
+            |a, name| a.parse_args::<syn::Expr>().map(|s| (name.clone(), s)),
+        ) {
+            match r {
+                Ok(strategy) => self.0.push(strategy),
+                Err(err) => self.1.push(err),
+            }
+        }
+
+        syn::visit_mut::visit_fn_arg_mut(self, node);
+    }
+}
+
+pub(crate) fn extract_strategies(
+    item_fn: &mut ItemFn,
+) -> Result<Vec<(Ident, syn::Expr)>, ErrorsVec> {
+    let mut extractor = StrategyFunctionExtractor::default();
+    extractor.visit_item_fn_mut(item_fn);
+
+    if extractor.1.is_empty() {
+        Ok(extractor.0)
+    } else {
+        Err(extractor.1.into())
+    }
+}
+
 /// Simple struct used to visit function args attributes to extract the
 /// excluded ones and eventualy parsing errors
 struct ExcludedTraceAttributesFunctionExtractor(Result<Vec<Ident>, ErrorsVec>);
@@ -598,36 +913,97 @@ impl Default for CheckTimeoutAttributesFunction {
     }
 }
 
-impl VisitMut for CheckTimeoutAttributesFunction {
-    fn visit_item_fn_mut(&mut self, node: &mut ItemFn) {
-        let timeouts = node
-            .attrs
+impl CheckTimeoutAttributesFunction {
+    /// Check the timeout attributes of the test or of one of its cases
+    fn check_attrs(&mut self, attrs: &[syn::Attribute], asyncness: Option<&Async>) {
+        let timeouts = attrs
             .iter()
             .filter(|&a| attr_is(a, "timeout"))
             .collect::<Vec<_>>();
-        let mut errors = timeouts
-            .iter()
-            .map(|&attr| attr.parse_args::<syn::Expr>())
-            .filter_map(Result::err)
-            .collect::<Vec<_>>();
+        let mut errors = vec![];
+        for attr in timeouts.iter() {
+            match attr.parse_args::<Timeout>() {
+                Ok(timeout) if timeout.mode == TimeoutMode::Watchdog => {
+                    if let Some(a) = asyncness {
+                        errors.push(syn::Error::new(
+                            a.span,
+                            "#[timeout(..., mode = watchdog)] cannot be used in async tests: async timeouts don't need Send arguments",
+                        ))
+                    }
+                }
+                Ok(_) => {}
+                Err(e) => errors.push(e),
+            }
+        }
 
-        if let Some(e) =
-            self.check_if_can_implement_timeous(timeouts.as_slice(), node.sig.asyncness.as_ref())
-        {
+        if let Some(e) = self.check_if_can_implement_timeous(timeouts.as_slice(), asyncness) {
             errors.push(e);
         }
-        if !errors.is_empty() {
-            *self = Self(Err(errors.into()));
+        errors.extend(
+            attrs
+                .iter()
+                .filter(|&a| attr_is(a, "no_timeout") && !a.tokens.is_empty())
+                .map(|a| syn::Error::new_spanned(a, "#[no_timeout] doesn't take any argument")),
+        );
+        match &mut self.0 {
+            _ if errors.is_empty() => {}
+            Err(all) => all.extend(errors),
+            result => *result = Err(errors.into()),
         }
     }
 }
 
-pub(crate) fn check_timeout_attrs(item_fn: &mut ItemFn) -> Result<(), ErrorsVec> {
+impl VisitMut for CheckTimeoutAttributesFunction {
+    fn visit_item_fn_mut(&mut self, node: &mut ItemFn) {
+        self.check_attrs(&node.attrs, node.sig.asyncness.as_ref());
+    }
+}
+
+/// Check the test's timeout attributes and the ones that the cases override.
+pub(crate) fn check_timeout_attrs<'a>(
+    item_fn: &mut ItemFn,
+    cases: impl Iterator<Item = &'a TestCase>,
+) -> Result<(), ErrorsVec> {
     let mut checker = CheckTimeoutAttributesFunction::default();
     checker.visit_item_fn_mut(item_fn);
+    for case in cases {
+        checker.check_attrs(&case.attrs, item_fn.sig.asyncness.as_ref());
+    }
     checker.take()
 }
 
+/// Parse the `n` of `#[retries(n)]`: the number of attempts after the first one.
+pub(crate) fn parse_retries(attr: &syn::Attribute) -> syn::Result<u32> {
+    attr.parse_args::<syn::LitInt>()
+        .and_then(|n| n.base10_parse())
+        .map_err(|e| {
+            syn::Error::new(
+                e.span(),
+                "#[retries(n)] needs the number of retries as integer literal",
+            )
+        })
+}
+
+/// Check the test's retries attributes and the ones that the cases override.
+pub(crate) fn check_retries_attrs<'a>(
+    item_fn: &mut ItemFn,
+    cases: impl Iterator<Item = &'a TestCase>,
+) -> Result<(), ErrorsVec> {
+    let errors = item_fn
+        .attrs
+        .iter()
+        .chain(cases.flat_map(|case| case.attrs.iter()))
+        .filter(|&a| attr_is(a, "retries"))
+        .map(parse_retries)
+        .filter_map(Result::err)
+        .collect::<Vec<_>>();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.into())
+    }
+}
+
 #[cfg(test)]
 mod should {
     use super::*;
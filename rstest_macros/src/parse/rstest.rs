@@ -3,16 +3,21 @@ use syn::{
     Ident, ItemFn, Token,
 };
 
+use super::exclude::Exclusion;
+use super::table::Table;
 use super::testcase::TestCase;
 use super::{
-    check_timeout_attrs, extract_case_args, extract_cases, extract_excluded_trace,
-    extract_fixtures, extract_value_list, parse_vector_trailing_till_double_comma, Attribute,
-    Attributes, ExtendWithFunctionAttrs, Fixture,
+    check_retries_attrs, check_timeout_attrs, extract_case_args, extract_cases,
+    extract_excluded_trace, extract_exclusions, extract_fixtures, extract_matrix_modes,
+    extract_strategies, extract_tables, extract_value_list,
+    parse_vector_trailing_till_double_comma, Attribute, Attributes, ExtendWithFunctionAttrs,
+    Fixture,
 };
 use crate::parse::vlist::ValueList;
 use crate::{
     error::ErrorsVec,
     refident::{MaybeIdent, RefIdent},
+    utils::fn_args_has_ident,
 };
 use proc_macro2::{Span, TokenStream};
 use quote::{format_ident, ToTokens};
@@ -26,7 +31,7 @@ pub(crate) struct RsTestInfo {
 impl Parse for RsTestInfo {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         Ok(if input.is_empty() {
-            Default::default()
+            Self::default()
         } else {
             Self {
                 data: input.parse()?,
@@ -41,16 +46,46 @@ impl Parse for RsTestInfo {
 
 impl ExtendWithFunctionAttrs for RsTestInfo {
     fn extend_with_function_attrs(&mut self, item_fn: &mut ItemFn) -> Result<(), ErrorsVec> {
-        let (_, (excluded, _)) = merge_errors!(
+        self.extract_auto_names_argument(item_fn);
+        // Extract matrix modes and exclusions before the cases: otherwise they would
+        // become case attributes
+        let (modes, (exclusions, (_, (excluded, (strategies, _))))) = merge_errors!(
+            extract_matrix_modes(item_fn),
+            extract_exclusions(item_fn),
             self.data.extend_with_function_attrs(item_fn),
             extract_excluded_trace(item_fn),
-            check_timeout_attrs(item_fn)
+            extract_strategies(item_fn),
+            check_timeout_attrs(item_fn, self.data.cases()),
+            check_retries_attrs(item_fn, self.data.cases())
         )?;
         self.attributes.add_notraces(excluded);
+        self.attributes.add_strategies(strategies);
+        self.attributes.add_matrix_modes(modes);
+        self.data
+            .items
+            .extend(exclusions.into_iter().map(|e| e.into()));
         Ok(())
     }
 }
 
+impl RsTestInfo {
+    /// `#[rstest(auto_names)]` is parsed as a case argument name: if the function
+    /// has no argument with this name it's the `auto_names` attribute instead.
+    fn extract_auto_names_argument(&mut self, item_fn: &ItemFn) {
+        let auto_names = format_ident!("{}", RsTestAttributes::AUTO_NAMES_ATTR);
+        if fn_args_has_ident(item_fn, &auto_names) {
+            return;
+        }
+        let len = self.data.items.len();
+        self.data
+            .items
+            .retain(|it| !matches!(it, RsTestItem::CaseArgName(arg) if arg == &auto_names));
+        if self.data.items.len() != len {
+            self.attributes.add_auto_names();
+        }
+    }
+}
+
 #[derive(PartialEq, Debug, Default)]
 pub(crate) struct RsTestData {
     pub(crate) items: Vec<RsTestItem>,
@@ -102,6 +137,20 @@ impl RsTestData {
     pub(crate) fn has_list_values(&self) -> bool {
         self.list_values().next().is_some()
     }
+
+    pub(crate) fn tables(&self) -> impl Iterator<Item = &Table> {
+        self.items.iter().filter_map(|it| match it {
+            RsTestItem::Table(ref table) => Some(table),
+            _ => None,
+        })
+    }
+
+    pub(crate) fn exclusions(&self) -> impl Iterator<Item = &Exclusion> {
+        self.items.iter().filter_map(|it| match it {
+            RsTestItem::Exclusion(ref exclusion) => Some(exclusion),
+            _ => None,
+        })
+    }
 }
 
 impl Parse for RsTestData {
@@ -118,7 +167,9 @@ impl Parse for RsTestData {
 
 impl ExtendWithFunctionAttrs for RsTestData {
     fn extend_with_function_attrs(&mut self, item_fn: &mut ItemFn) -> Result<(), ErrorsVec> {
-        let composed_tuple!(fixtures, case_args, cases, value_list) = merge_errors!(
+        // Extract tables before the cases: otherwise they would become case attributes
+        let composed_tuple!(tables, fixtures, case_args, cases, value_list) = merge_errors!(
+            extract_tables(item_fn),
             extract_fixtures(item_fn),
             extract_case_args(item_fn),
             extract_cases(item_fn),
@@ -129,7 +180,36 @@ impl ExtendWithFunctionAttrs for RsTestData {
         self.items.extend(case_args.into_iter().map(|f| f.into()));
         self.items.extend(cases.into_iter().map(|f| f.into()));
         self.items.extend(value_list.into_iter().map(|f| f.into()));
-        Ok(())
+        let case_args = self.case_args().cloned().collect::<Vec<_>>();
+        let case_args = case_args.iter().collect::<Vec<_>>();
+        let mut errors = vec![];
+        for table in tables {
+            match table.cases(&case_args) {
+                Ok(cases) => self.items.extend(cases.into_iter().map(|c| c.into())),
+                Err(err) => errors.push(err),
+            }
+            self.items.push(table.into());
+        }
+        let values_args = self
+            .list_values()
+            .map(|v| v.arg.clone())
+            .collect::<Vec<_>>();
+        let values_args = values_args.iter().collect::<Vec<_>>();
+        for item in self.items.iter_mut() {
+            if let RsTestItem::TestCase(case) = item {
+                if let Err(err) = case.extract_values_overrides(&values_args) {
+                    errors.push(err);
+                }
+                if let Err(err) = case.extract_text_description() {
+                    errors.push(err);
+                }
+            }
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors.into())
+        }
     }
 }
 
@@ -139,6 +219,8 @@ pub(crate) enum RsTestItem {
     CaseArgName(Ident),
     TestCase(TestCase),
     ValueList(ValueList),
+    Exclusion(Exclusion),
+    Table(Table),
 }
 
 impl From<Fixture> for RsTestItem {
@@ -165,6 +247,18 @@ impl From<ValueList> for RsTestItem {
     }
 }
 
+impl From<Exclusion> for RsTestItem {
+    fn from(exclusion: Exclusion) -> Self {
+        RsTestItem::Exclusion(exclusion)
+    }
+}
+
+impl From<Table> for RsTestItem {
+    fn from(table: Table) -> Self {
+        RsTestItem::Table(table)
+    }
+}
+
 impl Parse for RsTestItem {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         if input.fork().parse::<TestCase>().is_ok() {
@@ -188,7 +282,7 @@ impl MaybeIdent for RsTestItem {
             Fixture(ref fixture) => Some(fixture.ident()),
             CaseArgName(ref case_arg) => Some(case_arg),
             ValueList(ref value_list) => Some(value_list.ident()),
-            TestCase(_) => None,
+            TestCase(_) | Exclusion(_) | Table(_) => None,
         }
     }
 }
@@ -201,6 +295,8 @@ impl ToTokens for RsTestItem {
             CaseArgName(ref case_arg) => case_arg.to_tokens(tokens),
             TestCase(ref case) => case.to_tokens(tokens),
             ValueList(ref list) => list.to_tokens(tokens),
+            Exclusion(ref exclusion) => exclusion.to_tokens(tokens),
+            Table(ref table) => table.to_tokens(tokens),
         }
     }
 }
@@ -210,15 +306,21 @@ wrap_attributes!(RsTestAttributes);
 impl RsTestAttributes {
     const TRACE_VARIABLE_ATTR: &'static str = "trace";
     const NOTRACE_VARIABLE_ATTR: &'static str = "notrace";
+    const AUTO_NAMES_ATTR: &'static str = "auto_names";
+    pub(crate) const MATRIX_ATTR: &'static str = "matrix";
 
     pub(crate) fn trace_me(&self, ident: &Ident) -> bool {
         if self.should_trace() {
-            !self.iter().any(|m| Self::is_notrace(ident, m))
+            !self.is_notraced(ident)
         } else {
             false
         }
     }
 
+    pub(crate) fn is_notraced(&self, ident: &Ident) -> bool {
+        self.iter().any(|m| Self::is_notrace(ident, m))
+    }
+
     fn is_notrace(ident: &Ident, m: &Attribute) -> bool {
         match m {
             Attribute::Tagged(i, args) if i == Self::NOTRACE_VARIABLE_ATTR => {
@@ -249,6 +351,98 @@ impl RsTestAttributes {
     fn is_trace(m: &Attribute) -> bool {
         matches!(m, Attribute::Attr(i) if i == Self::TRACE_VARIABLE_ATTR)
     }
+
+    /// Should derive the tests names from the literal values?
+    /// Enabled by the `auto_names` attribute or for every test by the
+    /// `auto-names` feature.
+    pub(crate) fn auto_names(&self) -> bool {
+        cfg!(feature = "auto-names")
+            || self
+                .iter()
+                .any(|m| matches!(m, Attribute::Attr(i) if i == Self::AUTO_NAMES_ATTR))
+    }
+
+    pub(crate) fn add_auto_names(&mut self) {
+        if !self.auto_names() {
+            self.inner
+                .attributes
+                .push(Attribute::Attr(format_ident!("{}", Self::AUTO_NAMES_ATTR)));
+        }
+    }
+
+    /// All the `matrix(mode)` attributes: more than one or an invalid mode are
+    /// reported as errors.
+    pub(crate) fn matrix_modes(&self) -> impl Iterator<Item = (&Ident, &Vec<Ident>)> {
+        self.iter().filter_map(|m| match m {
+            Attribute::Tagged(i, args) if i == Self::MATRIX_ATTR => Some((i, args)),
+            _ => None,
+        })
+    }
+
+    /// How to combine the values lists: the cartesian product if not given.
+    pub(crate) fn matrix_mode(&self) -> MatrixMode {
+        self.matrix_modes()
+            .find_map(|(_, args)| match args.as_slice() {
+                [mode] => mode.to_string().parse().ok(),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    /// The `#[strategy(expr)]` arguments: they are stored as `Expr(arg, expr)`
+    /// attributes.
+    pub(crate) fn strategies(&self) -> impl Iterator<Item = (&Ident, &syn::Expr)> {
+        self.iter().filter_map(|m| match m {
+            Attribute::Expr(arg, strategy) => Some((arg, strategy.as_ref())),
+            _ => None,
+        })
+    }
+
+    pub(crate) fn add_strategies(&mut self, strategies: Vec<(Ident, syn::Expr)>) {
+        self.inner.attributes.extend(
+            strategies
+                .into_iter()
+                .map(|(arg, strategy)| Attribute::Expr(arg, Box::new(strategy))),
+        );
+    }
+
+    pub(crate) fn add_matrix_modes(&mut self, modes: Vec<Ident>) {
+        for mode in modes {
+            self.inner.attributes.push(Attribute::Tagged(
+                Ident::new(Self::MATRIX_ATTR, mode.span()),
+                vec![mode],
+            ));
+        }
+    }
+}
+
+/// How the values lists are combined to generate the tests.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub(crate) enum MatrixMode {
+    /// Every combination of the values (the default).
+    #[default]
+    Product,
+    /// The i-th test takes the i-th value of every list.
+    Zip,
+    /// Every pair of values of two different lists is tested at least once.
+    Pairwise,
+}
+
+impl MatrixMode {
+    pub(crate) const MODES: &'static [&'static str] = &["product", "zip", "pairwise"];
+}
+
+impl std::str::FromStr for MatrixMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "product" => Ok(Self::Product),
+            "zip" => Ok(Self::Zip),
+            "pairwise" => Ok(Self::Pairwise),
+            _ => Err(()),
+        }
+    }
 }
 
 impl Parse for RsTestAttributes {
@@ -301,6 +495,102 @@ mod test {
         assert_eq!(2, errors.len());
     }
 
+    #[test]
+    fn should_reject_no_timeout_with_arguments() {
+        let mut item_fn = r#"
+            #[no_timeout]
+            #[no_timeout(42)]
+            fn test_fn() {
+            }
+        "#
+        .ast();
+
+        let mut info = RsTestInfo::default();
+
+        let errors = info.extend_with_function_attrs(&mut item_fn).unwrap_err();
+
+        assert_eq!(1, errors.len());
+        assert!(format!("{:?}", errors).contains("#[no_timeout] doesn't take any argument"))
+    }
+
+    #[test]
+    fn should_reject_invalid_retries() {
+        let mut item_fn = r#"
+            #[retries(3)]
+            #[retries]
+            #[retries(-1)]
+            #[retries(some_const)]
+            fn test_fn() {
+            }
+        "#
+        .ast();
+
+        let mut info = RsTestInfo::default();
+
+        let errors = info.extend_with_function_attrs(&mut item_fn).unwrap_err();
+
+        assert_eq!(3, errors.len());
+        assert!(format!("{:?}", errors).contains("#[retries(n)] needs the number of retries"))
+    }
+
+    #[test]
+    fn should_reject_watchdog_timeout_in_async_tests() {
+        let mut item_fn = r#"
+            #[timeout(Duration::from_millis(20), mode = watchdog)]
+            async fn test_fn() {
+            }
+        "#
+        .ast();
+
+        let mut info = RsTestInfo::default();
+
+        let errors = info.extend_with_function_attrs(&mut item_fn).unwrap_err();
+
+        assert!(format!("{:?}", errors).contains("mode = watchdog)] cannot be used in async tests"))
+    }
+
+    #[test]
+    fn should_reject_invalid_case_timeouts() {
+        let mut item_fn = r#"
+            #[timeout(Duration::from_millis(20), mode = watchdog)]
+            #[case(1)]
+            #[timeout(Duration::from_millis(20), mode = never)]
+            #[case(2)]
+            #[timeout]
+            #[case(3)]
+            async fn test_fn(#[case] a: u32) {
+            }
+        "#
+        .ast();
+
+        let mut info = RsTestInfo::default();
+
+        let errors = info.extend_with_function_attrs(&mut item_fn).unwrap_err();
+
+        assert_eq!(3, errors.len());
+        assert!(format!("{:?}", errors).contains("mode = watchdog)] cannot be used in async tests"))
+    }
+
+    #[test]
+    fn should_reject_invalid_case_retries() {
+        let mut item_fn = r#"
+            #[retries(2)]
+            #[case(1)]
+            #[retries(-1)]
+            #[case(2)]
+            fn test_fn(#[case] a: u32) {
+            }
+        "#
+        .ast();
+
+        let mut info = RsTestInfo::default();
+
+        let errors = info.extend_with_function_attrs(&mut item_fn).unwrap_err();
+
+        assert_eq!(1, errors.len());
+        assert!(format!("{:?}", errors).contains("#[retries(n)] needs the number of retries"))
+    }
+
     #[cfg(feature = "async-timeout")]
     #[test]
     fn should_parse_async_timeout() {
@@ -465,6 +755,107 @@ mod test {
             assert_eq!(expected, data);
         }
 
+        #[rstest]
+        #[case::alone("::auto_names")]
+        #[case::with_fixtures("my_fixture::auto_names")]
+        #[case::with_other_attributes("::trace::auto_names")]
+        fn auto_names(#[case] code: &str) {
+            let data = parse_rstest(code);
+
+            assert!(data.attributes.auto_names());
+            assert!(data.data.fixtures().all(|f| f.name != "auto_names"));
+        }
+
+        #[test]
+        fn auto_names_argument_is_a_case_argument() {
+            let data = parse_rstest("auto_names, case(42)");
+
+            assert!(!data.attributes.auto_names());
+            assert_eq!(
+                vec!["auto_names"],
+                data.data.case_args().map(ToString::to_string).collect::<Vec<_>>()
+            );
+        }
+
+        #[test]
+        fn bare_auto_names_is_the_attribute_if_not_a_function_argument() {
+            let mut item_fn = r#"
+            #[case(42)]
+            fn test_fn(#[case] n: u32) {
+            }
+            "#
+            .ast();
+            let mut info = parse_rstest("auto_names");
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            assert!(info.attributes.auto_names());
+            assert_eq!(
+                vec!["n"],
+                info.data
+                    .case_args()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+            );
+        }
+
+        #[test]
+        fn bare_auto_names_is_a_case_argument_if_the_function_has_it() {
+            let mut item_fn = r#"
+            #[case(42)]
+            fn test_fn(auto_names: u32) {
+            }
+            "#
+            .ast();
+            let mut info = parse_rstest("auto_names");
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            assert!(!info.attributes.auto_names());
+            assert_eq!(
+                vec!["auto_names"],
+                info.data
+                    .case_args()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+            );
+        }
+
+        #[rstest]
+        #[case::default("", MatrixMode::Product)]
+        #[case::zip("::matrix(zip)", MatrixMode::Zip)]
+        #[case::pairwise("a => [1, 2]::matrix(pairwise)", MatrixMode::Pairwise)]
+        fn matrix_mode(#[case] code: &str, #[case] expected: MatrixMode) {
+            let data = parse_rstest(code);
+
+            assert_eq!(expected, data.attributes.matrix_mode());
+        }
+
+        #[test]
+        fn extract_matrix_mode_attribute() {
+            let mut item_fn = r#"
+            #[matrix(pairwise)]
+            fn test_fn(#[values(1, 2)] a: u32, #[values(1, 2)] b: u32, #[values(1, 2)] c: u32) {
+            }
+            "#
+            .ast();
+
+            let mut info = RsTestInfo::default();
+
+            info.extend_with_function_attrs(&mut item_fn).unwrap();
+
+            assert_eq!(MatrixMode::Pairwise, info.attributes.matrix_mode());
+            assert!(item_fn.attrs.is_empty());
+        }
+
+        #[test]
+        fn auto_names_fixture_with_arguments_is_a_fixture() {
+            let data = parse_rstest("auto_names(42)");
+
+            assert!(!data.attributes.auto_names());
+            assert_eq!(1, data.data.fixtures().count());
+        }
+
         #[test]
         fn empty_attributes() {
             let data = parse_rstest(r#"my_fixture(42, "other")"#);
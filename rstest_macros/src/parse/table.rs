@@ -0,0 +1,454 @@
+//! `#[table("path")]` attribute: the CSV, JSON or TOML file is read at expansion time
+//! relative to `CARGO_MANIFEST_DIR` and every row becomes a case. The columns are
+//! mapped to the `#[case]` arguments by name and every cell is injected as a literal
+//! string, so the values are converted by the `FromStr` magic conversion.
+use std::path::Path;
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream, Result},
+    Expr, Ident, LitStr,
+};
+
+use super::testcase::TestCase;
+
+/// The row's column that becomes the case description if it's not a case argument.
+const NAME_COLUMN: &str = "name";
+
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct Table {
+    path: LitStr,
+}
+
+/// The cells of a row in the columns order.
+type Row = Vec<(String, String)>;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+enum Format {
+    Csv,
+    Json,
+    Toml,
+}
+
+impl Parse for Table {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let path: LitStr = input.parse()?;
+        let table = Self { path };
+        table.format()?;
+        Ok(table)
+    }
+}
+
+impl Table {
+    fn format(&self) -> Result<Format> {
+        match Path::new(&self.path.value())
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase())
+            .as_deref()
+        {
+            Some("csv") => Ok(Format::Csv),
+            Some("json") => Ok(Format::Json),
+            Some("toml") => Ok(Format::Toml),
+            _ => Err(self.error("Invalid #[table] file: supported extensions are csv, json, toml")),
+        }
+    }
+
+    fn error(&self, message: impl std::fmt::Display) -> syn::Error {
+        syn::Error::new(self.path.span(), message)
+    }
+
+    /// Read the table relative to `CARGO_MANIFEST_DIR` and build a case for every row
+    pub(crate) fn cases(&self, case_args: &[&Ident]) -> Result<Vec<TestCase>> {
+        let base_dir = std::env::var("CARGO_MANIFEST_DIR").map_err(|_| {
+            self.error("Cannot read #[table] file: CARGO_MANIFEST_DIR is not defined")
+        })?;
+        let content = std::fs::read_to_string(Path::new(&base_dir).join(self.path.value()))
+            .map_err(|e| self.error(format!("Cannot read #[table] file: {}", e)))?;
+        let rows = match self.format()? {
+            Format::Csv => parse_csv(&content),
+            Format::Json => parse_json(&content),
+            Format::Toml => parse_toml(&content),
+        }
+        .map_err(|e| self.error(format!("Invalid #[table] file: {}", e)))?;
+        if rows.is_empty() {
+            return Err(self.error("Invalid #[table] file: no rows found"));
+        }
+        rows.iter()
+            .enumerate()
+            .map(|(pos, row)| self.case(pos + 1, row, case_args))
+            .collect()
+    }
+
+    fn case(&self, n: usize, row: &Row, case_args: &[&Ident]) -> Result<TestCase> {
+        let cell = |column: &str| {
+            row.iter()
+                .find(|(c, _)| c == column)
+                .map(|(_, v)| LitStr::new(v, self.path.span()))
+        };
+        let args = case_args
+            .iter()
+            .map(|arg| {
+                cell(&arg.to_string())
+                    .map(|value| -> Expr {
+                        syn::parse_quote! { #value }
+                    })
+                    .ok_or_else(|| self.error(format!("Missing column '{}' in the row {}", arg, n)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let text_description = Some(NAME_COLUMN)
+            .filter(|name| case_args.iter().all(|arg| arg != name))
+            .and_then(cell);
+        Ok(TestCase {
+            args,
+            attrs: vec![],
+            description: match text_description {
+                Some(_) => None,
+                None => Some(format_ident!("row_{}", n)),
+            },
+            text_description,
+            overrides: vec![],
+        })
+    }
+
+    /// The tests should be compiled again when the table changes
+    pub(crate) fn track(&self) -> TokenStream {
+        let path = &self.path;
+        quote! {
+            const _: &[u8] = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/", #path));
+        }
+    }
+}
+
+impl ToTokens for Table {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let path = &self.path;
+        tokens.extend(quote! { table(#path) })
+    }
+}
+
+/// The first record is the header; fields can be quoted by `"` and a `""` in a quoted
+/// field is a quote. Unquoted fields are trimmed and empty lines are ignored.
+fn parse_csv(content: &str) -> std::result::Result<Vec<Row>, String> {
+    let mut records = csv_records(content)?.into_iter();
+    let header = match records.next() {
+        Some((_, header)) => header,
+        None => return Err("the CSV header is missing".to_string()),
+    };
+    records
+        .map(|(line, fields)| {
+            if fields.len() != header.len() {
+                Err(format!(
+                    "line {} has {} fields but the header has {}",
+                    line,
+                    fields.len(),
+                    header.len()
+                ))
+            } else {
+                Ok(header.iter().cloned().zip(fields).collect())
+            }
+        })
+        .collect()
+}
+
+/// The records with their starting line
+fn csv_records(content: &str) -> std::result::Result<Vec<(usize, Vec<String>)>, String> {
+    let mut records = vec![];
+    let mut chars = content.chars().peekable();
+    let mut line = 1;
+    while chars.peek().is_some() {
+        let start = line;
+        let mut fields = vec![];
+        let mut field = String::new();
+        let (mut quoted, mut in_quotes) = (false, false);
+        while let Some(c) = chars.next() {
+            match c {
+                '"' if in_quotes => {
+                    if chars.peek() == Some(&'"') {
+                        chars.next();
+                        field.push('"');
+                    } else {
+                        in_quotes = false;
+                    }
+                }
+                '"' if !quoted && field.trim().is_empty() => {
+                    field.clear();
+                    quoted = true;
+                    in_quotes = true;
+                }
+                '\n' if !in_quotes => {
+                    line += 1;
+                    break;
+                }
+                '\r' if !in_quotes => {}
+                ',' if !in_quotes => {
+                    fields.push(csv_field(std::mem::take(&mut field), quoted));
+                    quoted = false;
+                }
+                c => {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    field.push(c);
+                }
+            }
+        }
+        if in_quotes {
+            return Err(format!("line {} has an unterminated quoted field", start));
+        }
+        if fields.is_empty() && !quoted && field.trim().is_empty() {
+            continue;
+        }
+        fields.push(csv_field(field, quoted));
+        records.push((start, fields));
+    }
+    Ok(records)
+}
+
+fn csv_field(field: String, quoted: bool) -> String {
+    if quoted {
+        field
+    } else {
+        field.trim().to_string()
+    }
+}
+
+#[cfg(feature = "table-json")]
+fn parse_json(content: &str) -> std::result::Result<Vec<Row>, String> {
+    use serde_json::Value;
+
+    let rows = match serde_json::from_str(content).map_err(|e| e.to_string())? {
+        Value::Array(rows) => rows,
+        _ => return Err("the JSON file should contain an array of objects".to_string()),
+    };
+    rows.into_iter()
+        .enumerate()
+        .map(|(pos, row)| match row {
+            Value::Object(cells) => cells
+                .into_iter()
+                .map(|(column, value)| {
+                    let value = match value {
+                        Value::String(s) => s,
+                        Value::Number(n) => n.to_string(),
+                        Value::Bool(b) => b.to_string(),
+                        _ => return Err(invalid_cell(pos + 1, &column)),
+                    };
+                    Ok((column, value))
+                })
+                .collect(),
+            _ => Err(format!("the row {} is not a JSON object", pos + 1)),
+        })
+        .collect()
+}
+
+#[cfg(not(feature = "table-json"))]
+fn parse_json(_content: &str) -> std::result::Result<Vec<Row>, String> {
+    Err("Enable table-json feature to use JSON tables".to_string())
+}
+
+/// The rows are the `[[case]]` array of tables
+#[cfg(feature = "table-toml")]
+fn parse_toml(content: &str) -> std::result::Result<Vec<Row>, String> {
+    use toml::Value;
+
+    let rows = match content
+        .parse::<Value>()
+        .map_err(|e| e.to_string())?
+        .get("case")
+    {
+        Some(Value::Array(rows)) => rows.clone(),
+        _ => return Err("the TOML file should contain a [[case]] array of tables".to_string()),
+    };
+    rows.into_iter()
+        .enumerate()
+        .map(|(pos, row)| match row {
+            Value::Table(cells) => cells
+                .into_iter()
+                .map(|(column, value)| {
+                    let value = match value {
+                        Value::String(s) => s,
+                        Value::Integer(i) => i.to_string(),
+                        Value::Float(f) => f.to_string(),
+                        Value::Boolean(b) => b.to_string(),
+                        Value::Datetime(d) => d.to_string(),
+                        _ => return Err(invalid_cell(pos + 1, &column)),
+                    };
+                    Ok((column, value))
+                })
+                .collect(),
+            _ => Err(format!("the case {} is not a TOML table", pos + 1)),
+        })
+        .collect()
+}
+
+#[cfg(not(feature = "table-toml"))]
+fn parse_toml(_content: &str) -> std::result::Result<Vec<Row>, String> {
+    Err("Enable table-toml feature to use TOML tables".to_string())
+}
+
+#[cfg(any(feature = "table-json", feature = "table-toml"))]
+fn invalid_cell(row: usize, column: &str) -> String {
+    format!(
+        "the column '{}' in the row {} should be a string, a number or a boolean",
+        column, row
+    )
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use rstest_test::assert_in;
+
+    fn rows<'a, R: AsRef<[(&'a str, &'a str)]>>(rows: &[R]) -> Vec<Row> {
+        rows.iter()
+            .map(|r| {
+                r.as_ref()
+                    .iter()
+                    .map(|(c, v)| (c.to_string(), v.to_string()))
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[rstest]
+    #[case::simple("a,b\n1,2\n3,4\n", vec![vec![("a", "1"), ("b", "2")], vec![("a", "3"), ("b", "4")]])]
+    #[case::trim_and_crlf("a , b\r\n 1 , 2 \r\n", vec![vec![("a", "1"), ("b", "2")]])]
+    #[case::skip_empty_lines("a,b\n\n1,2\n\n", vec![vec![("a", "1"), ("b", "2")]])]
+    #[case::no_trailing_newline("a\n1", vec![vec![("a", "1")]])]
+    #[case::empty_field("a,b\n,2\n", vec![vec![("a", ""), ("b", "2")]])]
+    #[case::quoted(
+        "a,b\n\"x, y\",\" say \"\"hi\"\" \"\n",
+        vec![vec![("a", "x, y"), ("b", r#" say "hi" "#)]]
+    )]
+    #[case::multiline("a\n\"x\ny\"\n", vec![vec![("a", "x\ny")]])]
+    fn parse_csv_rows(#[case] content: &str, #[case] expected: Vec<Vec<(&str, &str)>>) {
+        assert_eq!(rows(&expected), parse_csv(content).unwrap());
+    }
+
+    #[rstest]
+    #[case::no_header("", "header is missing")]
+    #[case::wrong_fields("a,b\n1,2\n3\n", "line 3 has 1 fields but the header has 2")]
+    #[case::unterminated("a\n\"x\n", "line 2 has an unterminated quoted field")]
+    fn raise_error_on_invalid_csv(#[case] content: &str, #[case] message: &str) {
+        assert_in!(parse_csv(content).unwrap_err(), message);
+    }
+
+    #[cfg(feature = "table-json")]
+    #[test]
+    fn parse_json_rows() {
+        let content = r#"[{"a": "x", "b": 42, "c": true}, {"a": "y", "b": -1.5, "c": false}]"#;
+
+        assert_eq!(
+            rows(&[
+                &[("a", "x"), ("b", "42"), ("c", "true")],
+                &[("a", "y"), ("b", "-1.5"), ("c", "false")]
+            ]),
+            parse_json(content).unwrap()
+        );
+    }
+
+    #[cfg(feature = "table-json")]
+    #[rstest]
+    #[case::not_an_array(r#"{"a": 1}"#, "array of objects")]
+    #[case::not_an_object(r#"[1]"#, "the row 1 is not a JSON object")]
+    #[case::nested(r#"[{"a": [1]}]"#, "the column 'a' in the row 1")]
+    fn raise_error_on_invalid_json(#[case] content: &str, #[case] message: &str) {
+        assert_in!(parse_json(content).unwrap_err(), message);
+    }
+
+    #[cfg(not(feature = "table-json"))]
+    #[test]
+    fn raise_error_on_json_if_feature_is_disabled() {
+        assert_in!(parse_json("[]").unwrap_err(), "Enable table-json feature");
+    }
+
+    #[cfg(feature = "table-toml")]
+    #[test]
+    fn parse_toml_rows() {
+        let content = r#"
+            [[case]]
+            a = "x"
+            b = 42
+
+            [[case]]
+            a = "y"
+            b = 1.5
+            "#;
+
+        assert_eq!(
+            rows(&[&[("a", "x"), ("b", "42")], &[("a", "y"), ("b", "1.5")]]),
+            parse_toml(content).unwrap()
+        );
+    }
+
+    #[cfg(feature = "table-toml")]
+    #[rstest]
+    #[case::no_cases("a = 1", "[[case]] array of tables")]
+    #[case::nested("[[case]]\na = [1]", "the column 'a' in the row 1")]
+    fn raise_error_on_invalid_toml(#[case] content: &str, #[case] message: &str) {
+        assert_in!(parse_toml(content).unwrap_err(), message);
+    }
+
+    #[cfg(not(feature = "table-toml"))]
+    #[test]
+    fn raise_error_on_toml_if_feature_is_disabled() {
+        assert_in!(parse_toml("").unwrap_err(), "Enable table-toml feature");
+    }
+
+    #[test]
+    fn raise_error_on_unknown_extension() {
+        let err = syn::parse_str::<Table>(r#""data.yaml""#).unwrap_err();
+
+        assert_in!(err.to_string(), "supported extensions are csv, json, toml");
+    }
+
+    mod build_case {
+        use super::assert_eq;
+        use super::*;
+
+        fn case(row: &[(&str, &str)], case_args: &[&str]) -> Result<TestCase> {
+            let table: Table = r#""data.csv""#.ast();
+            let case_args = case_args.iter().copied().map(ident).collect::<Vec<_>>();
+            table.case(3, &rows(&[row])[0], &case_args.iter().collect::<Vec<_>>())
+        }
+
+        #[test]
+        fn map_columns_to_case_args_by_name() {
+            let case = case(&[("b", "2"), ("a", "1"), ("other", "x")], &["a", "b"]).unwrap();
+
+            assert_eq!(
+                vec![r#""1""#, r#""2""#],
+                case.args
+                    .iter()
+                    .map(|a| a.display_code())
+                    .collect::<Vec<_>>()
+            );
+            assert_eq!("row_3", &case.description.unwrap().to_string());
+        }
+
+        #[test]
+        fn use_name_column_as_description() {
+            let case = case(&[("a", "1"), ("name", "first one")], &["a"]).unwrap();
+
+            assert!(case.description.is_none());
+            assert_eq!("first one", case.text_description.unwrap().value());
+        }
+
+        #[test]
+        fn not_use_name_column_if_it_is_a_case_argument() {
+            let case = case(&[("name", "x")], &["name"]).unwrap();
+
+            assert_eq!(1, case.args.len());
+            assert!(case.text_description.is_none());
+        }
+
+        #[test]
+        fn raise_error_on_missing_column() {
+            let err = case(&[("a", "1")], &["a", "b"]).unwrap_err();
+
+            assert_in!(err.to_string(), "Missing column 'b' in the row 3");
+        }
+    }
+}
@@ -1,12 +1,14 @@
 use syn::{
     parse::{Error, Parse, ParseStream, Result},
     punctuated::Punctuated,
-    Attribute, Expr, Ident, Token,
+    Attribute, Expr, Ident, LitStr, Token,
 };
 
 use proc_macro2::TokenStream;
 use quote::ToTokens;
 
+use super::vlist::ValueList;
+
 #[derive(PartialEq, Debug, Clone)]
 /// A test case instance data. Contains a list of arguments. It is parsed by parametrize
 /// attributes.
@@ -14,6 +16,81 @@ pub(crate) struct TestCase {
     pub(crate) args: Vec<Expr>,
     pub(crate) attrs: Vec<Attribute>,
     pub(crate) description: Option<Ident>,
+    pub(crate) text_description: Option<LitStr>,
+    pub(crate) overrides: Vec<ValueList>,
+}
+
+impl TestCase {
+    /// Extract the trailing `arg = [v1, ..., vn]` arguments: they replace the values list
+    /// of `arg` (that should be one of `values_args`) just for this case.
+    pub(crate) fn extract_values_overrides(&mut self, values_args: &[&Ident]) -> Result<()> {
+        while let Some(Expr::Assign(syn::ExprAssign { left, right, .. })) = self.args.last() {
+            let (arg, values) = match (left.as_ref(), right.as_ref()) {
+                (Expr::Path(path), Expr::Array(values)) if path.path.get_ident().is_some() => {
+                    (path.path.get_ident().unwrap().clone(), values)
+                }
+                _ => break,
+            };
+            if !values_args.contains(&&arg) {
+                return Err(Error::new_spanned(
+                    &arg,
+                    format!(
+                        "Cannot override '{}': it is not a values list argument",
+                        arg
+                    ),
+                ));
+            }
+            if values.elems.is_empty() {
+                return Err(Error::new_spanned(
+                    values,
+                    "Values list should not be empty",
+                ));
+            }
+            if self.overrides.iter().any(|o| o.arg == arg) {
+                return Err(Error::new_spanned(
+                    &arg,
+                    format!("Duplicate override of '{}' values", arg),
+                ));
+            }
+            let values = values.elems.iter().cloned().map(|v| v.into()).collect();
+            self.overrides.push(ValueList { arg, values });
+            self.args.pop();
+        }
+        self.overrides.reverse();
+        Ok(())
+    }
+
+    /// The values list of `arg` overridden by this case, if any
+    pub(crate) fn values_override(&self, arg: &Ident) -> Option<&ValueList> {
+        self.overrides.iter().find(|o| &o.arg == arg)
+    }
+
+    /// A case can start with a free text description, like in
+    /// `#[case(desc = "http 1.1 keep-alive", 1, 2)]`: the `desc` value should be a
+    /// literal string.
+    pub(crate) fn extract_text_description(&mut self) -> Result<()> {
+        let (left, right) = match self.args.first() {
+            Some(Expr::Assign(syn::ExprAssign { left, right, .. })) => (left, right),
+            _ => return Ok(()),
+        };
+        if !matches!(left.as_ref(), Expr::Path(path) if path.path.is_ident("desc")) {
+            return Ok(());
+        }
+        match right.as_ref() {
+            Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(text),
+                ..
+            }) => {
+                self.text_description = Some(text.clone());
+                self.args.remove(0);
+                Ok(())
+            }
+            other => Err(Error::new_spanned(
+                other,
+                "Case description should be a literal string",
+            )),
+        }
+    }
 }
 
 impl Parse for TestCase {
@@ -35,6 +112,8 @@ impl Parse for TestCase {
                 args,
                 attrs,
                 description,
+                text_description: None,
+                overrides: vec![],
             })
         } else {
             Err(Error::new(case.span(), "expected a test case"))
@@ -52,6 +131,7 @@ impl ToTokens for TestCase {
 mod should {
     use super::*;
     use crate::test::{assert_eq, *};
+    use rstest_test::assert_in;
 
     fn parse_test_case<S: AsRef<str>>(test_case: S) -> TestCase {
         parse_meta(test_case)
@@ -114,6 +194,84 @@ mod should {
         assert_eq!(to_args!(["42", "24"]), args);
     }
 
+    #[rstest]
+    #[case::text(r#"case(desc = "free text", 42, 24)"#, Some("free text"), &["42", "24"])]
+    #[case::with_description(r#"case::name(desc = "free text")"#, Some("free text"), &[])]
+    #[case::leading_string(
+        r#"case("value", 42, 24)"#,
+        None,
+        &[r#""value""#, "42", "24"]
+    )]
+    #[case::not_first(
+        r#"case(42, desc = "free text")"#,
+        None,
+        &["42", r#"desc = "free text""#]
+    )]
+    #[case::other_name(r#"case(name = "free text")"#, None, &[r#"name = "free text""#])]
+    fn extract_text_description(
+        #[case] code: &str,
+        #[case] expected: Option<&str>,
+        #[case] args: &[&str],
+    ) {
+        let mut test_case = parse_test_case(code);
+
+        test_case.extract_text_description().unwrap();
+
+        assert_eq!(
+            expected.map(ToOwned::to_owned),
+            test_case.text_description.as_ref().map(|t| t.value())
+        );
+        assert_eq!(to_args!(args), test_case.args());
+    }
+
+    #[test]
+    fn reject_not_literal_string_description() {
+        let mut test_case = parse_test_case(r#"case(desc = 42, 24)"#);
+
+        let err = test_case.extract_text_description().unwrap_err();
+
+        assert_in!(
+            err.to_string(),
+            "Case description should be a literal string"
+        );
+    }
+
+    #[test]
+    fn extract_values_overrides() {
+        let mut test_case = parse_test_case(r#"case(3, x = 4, mode = [Mode::A], other = [1, 2])"#);
+        let (mode, other) = (ident("mode"), ident("other"));
+
+        test_case
+            .extract_values_overrides(&[&mode, &other])
+            .unwrap();
+
+        assert_eq!(to_args!(["3", "x = 4"]), test_case.args());
+        assert_eq!(
+            vec![
+                values_list("mode", &["Mode::A"]),
+                values_list("other", &["1", "2"])
+            ],
+            test_case.overrides
+        );
+        assert_eq!(
+            Some(&values_list("other", &["1", "2"])),
+            test_case.values_override(&other)
+        );
+    }
+
+    #[rstest]
+    #[case::not_a_values_list(r#"case(3, unknown = [1])"#, "Cannot override 'unknown'")]
+    #[case::empty(r#"case(3, mode = [])"#, "should not be empty")]
+    #[case::duplicate(r#"case(3, mode = [1], mode = [2])"#, "Duplicate override of 'mode'")]
+    fn not_extract_invalid_values_overrides(#[case] code: &str, #[case] message: &str) {
+        let mut test_case = parse_test_case(code);
+        let mode = ident("mode");
+
+        let err = test_case.extract_values_overrides(&[&mode]).unwrap_err();
+
+        assert_in!(err.to_string(), message);
+    }
+
     #[test]
     fn parse_arbitrary_rust_code_as_expression() {
         let test_case = parse_test_case(
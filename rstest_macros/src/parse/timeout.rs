@@ -0,0 +1,103 @@
+//! `#[timeout(duration)]` attribute: `mode = watchdog` runs the test in its own thread
+//! under a watchdog instead of move it in a new thread, so the test's arguments don't
+//! need to be `Send`.
+use syn::{
+    parse::{Parse, ParseStream, Result},
+    Expr, Ident, Token,
+};
+
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct Timeout {
+    pub(crate) duration: Expr,
+    pub(crate) mode: TimeoutMode,
+}
+
+/// Where the test runs: in a new thread or in the test's thread watched by another one
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub(crate) enum TimeoutMode {
+    Thread,
+    Watchdog,
+}
+
+impl TimeoutMode {
+    const MODES: &'static [&'static str] = &["thread", "watchdog"];
+}
+
+impl Parse for TimeoutMode {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mode: Ident = input.parse()?;
+        if mode == "thread" {
+            Ok(TimeoutMode::Thread)
+        } else if mode == "watchdog" {
+            Ok(TimeoutMode::Watchdog)
+        } else {
+            Err(syn::Error::new_spanned(
+                mode,
+                format!(
+                    "Invalid #[timeout] mode: valid modes are {}",
+                    Self::MODES.join(", ")
+                ),
+            ))
+        }
+    }
+}
+
+impl Timeout {
+    const MODE: &'static str = "mode";
+}
+
+impl Parse for Timeout {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let duration = input.parse()?;
+        let mut mode = TimeoutMode::Thread;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let ident: Ident = input.parse()?;
+            if ident != Self::MODE {
+                return Err(syn::Error::new_spanned(
+                    ident,
+                    format!("Invalid #[timeout] argument: use {} = mode", Self::MODE),
+                ));
+            }
+            let _eq: Token![=] = input.parse()?;
+            mode = input.parse()?;
+            let _comma: Option<Token![,]> = input.parse()?;
+        }
+        Ok(Timeout { duration, mode })
+    }
+}
+
+#[cfg(test)]
+mod should {
+    use super::*;
+    use crate::test::{assert_eq, *};
+    use rstest_test::assert_in;
+
+    fn timeout(attr: &str) -> Timeout {
+        syn::parse_str(attr).unwrap()
+    }
+
+    #[rstest]
+    #[case::default("Duration::from_millis(10)", TimeoutMode::Thread)]
+    #[case::trailing_comma("Duration::from_millis(10),", TimeoutMode::Thread)]
+    #[case::thread("Duration::from_millis(10), mode = thread", TimeoutMode::Thread)]
+    #[case::watchdog("Duration::from_millis(10), mode = watchdog", TimeoutMode::Watchdog)]
+    fn parse_mode(#[case] attr: &str, #[case] expected: TimeoutMode) {
+        let timeout = timeout(attr);
+
+        assert_eq!(expected, timeout.mode);
+        assert_eq!(expr("Duration::from_millis(10)"), timeout.duration);
+    }
+
+    #[rstest]
+    #[case::unknown_argument("Duration::from_millis(10), other = 3", "Invalid #[timeout] argument")]
+    #[case::invalid_mode("Duration::from_millis(10), mode = process", "Invalid #[timeout] mode")]
+    #[case::string_mode(
+        r#"Duration::from_millis(10), mode = "watchdog""#,
+        "expected identifier"
+    )]
+    fn not_parse_invalid_attributes(#[case] attr: &str, #[case] message: &str) {
+        let err = syn::parse_str::<Timeout>(attr).unwrap_err();
+
+        assert_in!(err.to_string(), message);
+    }
+}
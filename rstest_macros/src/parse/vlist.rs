@@ -1,18 +1,195 @@
 use proc_macro2::TokenStream;
-use quote::ToTokens;
+use quote::{quote, ToTokens};
 use syn::{
+    ext::IdentExt,
     parse::{Parse, ParseStream, Result},
-    Expr, Ident, Token,
+    Attribute, Expr, Ident, Token,
 };
 
-use crate::refident::RefIdent;
-
-use super::expressions::Expressions;
+use crate::{refident::RefIdent, utils::attr_is};
 
 #[derive(Debug, PartialEq, Clone)]
 pub(crate) struct ValueList {
     pub(crate) arg: Ident,
-    pub(crate) values: Vec<Expr>,
+    pub(crate) values: Vec<Value>,
+}
+
+/// A value in a `#[values]` list: the optional description is used to
+/// compose the test name and the optional label replaces the value's index in it.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct Value {
+    pub(crate) expr: Expr,
+    pub(crate) description: Option<String>,
+    pub(crate) label: Option<Ident>,
+}
+
+impl Value {
+    pub(crate) fn new(expr: Expr, description: Option<String>) -> Self {
+        Self {
+            expr,
+            description,
+            label: None,
+        }
+    }
+
+    pub(crate) fn labeled(label: Ident, expr: Expr) -> Self {
+        Self {
+            label: Some(label),
+            ..Self::from(expr)
+        }
+    }
+}
+
+/// A value can be labeled both by `label => expr` and `#[name(label)] expr`
+impl Parse for Value {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut label = None;
+        for attr in input.call(Attribute::parse_outer)? {
+            if !attr_is(&attr, "name") {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "Invalid value attribute: just #[name(label)] is allowed",
+                ));
+            }
+            if label.is_some() {
+                return Err(syn::Error::new_spanned(
+                    attr,
+                    "You cannot use #[name] more than once",
+                ));
+            }
+            label = Some(attr.parse_args::<Ident>()?);
+        }
+        if input.peek(Ident::peek_any) && input.peek2(Token![=>]) {
+            let name = input.call(Ident::parse_any)?;
+            let _to: Token![=>] = input.parse()?;
+            if label.is_some() {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "Value is already labeled by #[name]",
+                ));
+            }
+            label = Some(name);
+        }
+        let expr = input.parse()?;
+        Ok(match label {
+            Some(label) => Self::labeled(label.unraw(), expr),
+            None => expr.into(),
+        })
+    }
+}
+
+/// The values of a `#[values(...)]` attribute: all labels should be different.
+pub(crate) struct Values(Vec<Value>);
+
+impl Values {
+    pub(crate) fn take(self) -> Vec<Value> {
+        self.0
+    }
+}
+
+impl Parse for Values {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let values = input
+            .parse_terminated::<_, Token![,]>(Value::parse)?
+            .into_iter()
+            .collect::<Vec<_>>();
+        let mut labels = std::collections::HashSet::new();
+        if let Some(duplicate) = values
+            .iter()
+            .filter_map(|v| v.label.as_ref())
+            .find(|&l| !labels.insert(l))
+        {
+            return Err(syn::Error::new_spanned(
+                duplicate,
+                format!("Duplicate value label '{}'", duplicate),
+            ));
+        }
+        Ok(Self(values))
+    }
+}
+
+/// A `#[values_from(SOURCE, len = N)]` attribute: the values are the clones of the `N`
+/// elements of the `SOURCE` const array or slice. The macro cannot read the source's
+/// length, so it should be given explicitly and it's checked at compile time.
+pub(crate) struct ValuesFrom {
+    source: Expr,
+    len: syn::LitInt,
+}
+
+impl ValuesFrom {
+    const LEN: &'static str = "len";
+
+    pub(crate) fn value_list(&self, arg: &Ident) -> Result<ValueList> {
+        let len = self.len.base10_parse::<usize>()?;
+        if len == 0 {
+            return Err(syn::Error::new_spanned(
+                &self.len,
+                "Values list should not be empty",
+            ));
+        }
+        let source = &self.source;
+        let message = format!(
+            "#[values_from] source should have {} elements as declared by len",
+            len
+        );
+        let values = (0..len)
+            .map(|i| {
+                let index = syn::Index::from(i);
+                let value = quote! { ::std::clone::Clone::clone(&#source[#index]) };
+                // All values are compiled together: check the length just in the first one
+                if i == 0 {
+                    syn::parse_quote! {
+                        {
+                            const _: () = assert!(#source.len() == #len, #message);
+                            #value
+                        }
+                    }
+                } else {
+                    syn::parse_quote! { #value }
+                }
+            })
+            .map(|expr: Expr| expr.into())
+            .collect();
+        Ok(ValueList {
+            arg: arg.clone(),
+            values,
+        })
+    }
+}
+
+impl Parse for ValuesFrom {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let source = input.parse()?;
+        let len = if input.is_empty() {
+            None
+        } else {
+            let _comma: Token![,] = input.parse()?;
+            let name: Ident = input.parse()?;
+            if name != Self::LEN {
+                return Err(syn::Error::new_spanned(
+                    name,
+                    "Invalid #[values_from] argument: just len = N is allowed",
+                ));
+            }
+            let _eq: Token![=] = input.parse()?;
+            let len = input.parse()?;
+            let _trailing: Option<Token![,]> = input.parse()?;
+            Some(len)
+        };
+        match len {
+            Some(len) => Ok(Self { source, len }),
+            None => Err(syn::Error::new_spanned(
+                source,
+                "#[values_from] needs the source length: use #[values_from(SOURCE, len = N)]",
+            )),
+        }
+    }
+}
+
+impl From<Expr> for Value {
+    fn from(expr: Expr) -> Self {
+        Self::new(expr, None)
+    }
 }
 
 impl Parse for ValueList {
@@ -21,7 +198,7 @@ impl Parse for ValueList {
         let _to: Token![=>] = input.parse()?;
         let content;
         let paren = syn::bracketed!(content in input);
-        let values: Expressions = content.parse()?;
+        let values: Values = content.parse()?;
 
         let ret = Self {
             arg,
@@ -53,6 +230,7 @@ impl ToTokens for ValueList {
 #[cfg(test)]
 mod should {
     use crate::test::{assert_eq, *};
+    use rstest_test::assert_in;
 
     use super::*;
 
@@ -96,10 +274,104 @@ mod should {
             parse_values_list(r#"other => [some:<>(1,2,3)]"#);
         }
 
+        #[test]
+        fn labeled_values() {
+            let values_list =
+                parse_values_list(r#"arg => [first => 1, #[name(second)] 2, 3, r#type => 4]"#);
+
+            assert_eq!(values_list.args(), to_args!(["1", "2", "3", "4"]));
+            assert_eq!(
+                vec![
+                    Some("first".to_string()),
+                    Some("second".to_string()),
+                    None,
+                    Some("type".to_string())
+                ],
+                values_list
+                    .values
+                    .iter()
+                    .map(|v| v.label.as_ref().map(|l| l.to_string()))
+                    .collect::<Vec<_>>()
+            );
+        }
+
+        #[rstest]
+        #[case::duplicate_label(r#"a => 1, a => 2"#, "Duplicate value label 'a'")]
+        #[case::invalid_attribute(r#"#[other(a)] 1"#, "Invalid value attribute")]
+        #[case::more_names(r#"#[name(a)] #[name(b)] 1"#, "more than once")]
+        #[case::name_and_label(r#"#[name(a)] b => 1"#, "already labeled")]
+        fn not_parse_invalid_labels(#[case] values: &str, #[case] message: &str) {
+            let err = syn::parse_str::<Values>(values).err().unwrap();
+
+            assert_in!(err.to_string(), message);
+        }
+
         #[test]
         #[should_panic(expected = r#"expected square brackets"#)]
         fn forget_brackets() {
             parse_values_list(r#"other => 42"#);
         }
     }
+
+    mod values_from {
+        use super::assert_eq;
+        use super::*;
+
+        #[test]
+        fn index_the_source() {
+            let values_from: ValuesFrom = "crate::CORNER_CASES, len = 3".ast();
+
+            let vlist = values_from.value_list(&ident("arg")).unwrap();
+
+            assert_eq!("arg", &vlist.arg.to_string());
+            assert_eq!(3, vlist.values.len());
+            for (i, value) in vlist.values.iter().enumerate() {
+                assert_in!(
+                    value.expr.display_code(),
+                    format!(
+                        ":: std :: clone :: Clone :: clone (& crate :: CORNER_CASES [{}])",
+                        i
+                    )
+                );
+            }
+        }
+
+        #[test]
+        fn check_the_source_length_just_once() {
+            let values_from: ValuesFrom = "crate::CORNER_CASES, len = 3".ast();
+
+            let vlist = values_from.value_list(&ident("arg")).unwrap();
+
+            let checks = vlist
+                .values
+                .iter()
+                .filter(|value| {
+                    value
+                        .expr
+                        .display_code()
+                        .contains("assert ! (crate :: CORNER_CASES . len () == 3usize")
+                })
+                .count();
+            assert_eq!(1, checks);
+        }
+
+        #[rstest]
+        #[case::without_len("SOURCE", "needs the source length")]
+        #[case::invalid_argument("SOURCE, size = 3", "just len = N is allowed")]
+        #[case::not_a_number("SOURCE, len = x", "expected integer literal")]
+        fn not_parse_invalid_attribute(#[case] code: &str, #[case] message: &str) {
+            let err = syn::parse_str::<ValuesFrom>(code).err().unwrap();
+
+            assert_in!(err.to_string(), message);
+        }
+
+        #[test]
+        fn raise_error_on_empty_source() {
+            let values_from: ValuesFrom = "SOURCE, len = 0".ast();
+
+            let err = values_from.value_list(&ident("arg")).unwrap_err();
+
+            assert_in!(err.to_string(), "Values list should not be empty");
+        }
+    }
 }
@@ -80,7 +80,7 @@ impl MaybeIdent for crate::parse::Attribute {
     fn maybe_ident(&self) -> Option<&Ident> {
         use crate::parse::Attribute::*;
         match self {
-            Attr(ident) | Tagged(ident, _) | Type(ident, _) => Some(ident),
+            Attr(ident) | Tagged(ident, _) | Type(ident, _) | Expr(ident, _) => Some(ident),
         }
     }
 }
@@ -0,0 +1,166 @@
+//! How to combine the values lists in the matrix modes: every combination is a vector
+//! of indexes, one for each values list.
+use std::{cmp::Reverse, collections::BTreeSet};
+
+use crate::parse::rstest::MatrixMode;
+
+/// Return the combinations of `lens` values lists sorted in lexicographic order.
+pub(crate) fn combinations(mode: MatrixMode, lens: &[usize]) -> Vec<Vec<usize>> {
+    match mode {
+        MatrixMode::Product => product(lens),
+        MatrixMode::Zip => zip(lens),
+        MatrixMode::Pairwise => pairwise(lens),
+    }
+}
+
+fn product(lens: &[usize]) -> Vec<Vec<usize>> {
+    lens.iter().fold(vec![vec![]], |combinations, &len| {
+        combinations
+            .into_iter()
+            .flat_map(|c| {
+                (0..len).map(move |i| {
+                    let mut c = c.clone();
+                    c.push(i);
+                    c
+                })
+            })
+            .collect()
+    })
+}
+
+/// Lists with different length are a compile error: here we just stop at the shortest one.
+fn zip(lens: &[usize]) -> Vec<Vec<usize>> {
+    let len = lens.iter().min().copied().unwrap_or_default();
+    (0..len).map(|i| vec![i; lens.len()]).collect()
+}
+
+/// A greedy all-pairs algorithm: every row starts from the first uncovered pair and
+/// takes, for each other list, the value that covers most of the uncovered pairs.
+/// It's not optimal but it's deterministic and the tests names never change if the
+/// lists don't.
+fn pairwise(lens: &[usize]) -> Vec<Vec<usize>> {
+    if lens.len() < 3 || lens.contains(&0) {
+        return product(lens);
+    }
+    let n = lens.len();
+    let mut uncovered = BTreeSet::new();
+    for i in 0..n {
+        for j in (i + 1)..n {
+            for a in 0..lens[i] {
+                for b in 0..lens[j] {
+                    uncovered.insert((i, a, j, b));
+                }
+            }
+        }
+    }
+
+    let mut rows = vec![];
+    while let Some(&(i, a, j, b)) = uncovered.iter().next() {
+        let mut row = vec![None; n];
+        row[i] = Some(a);
+        row[j] = Some(b);
+        for k in 0..n {
+            if row[k].is_some() {
+                continue;
+            }
+            let covered = |v: usize| {
+                row.iter()
+                    .enumerate()
+                    .filter_map(|(h, x)| x.map(|x| (h, x)))
+                    .filter(|&(h, x)| {
+                        let pair = if h < k { (h, x, k, v) } else { (k, v, h, x) };
+                        uncovered.contains(&pair)
+                    })
+                    .count()
+            };
+            row[k] = (0..lens[k]).max_by_key(|&v| (covered(v), Reverse(v)));
+        }
+        let row = row.into_iter().flatten().collect::<Vec<_>>();
+        for h in 0..n {
+            for k in (h + 1)..n {
+                uncovered.remove(&(h, row[h], k, row[k]));
+            }
+        }
+        rows.push(row);
+    }
+    rows.sort();
+    rows
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test::{assert_eq, *};
+
+    #[rstest]
+    #[case::empty(&[], vec![vec![]])]
+    #[case::one(&[3], vec![vec![0], vec![1], vec![2]])]
+    #[case::two(&[2, 2], vec![vec![0, 0], vec![0, 1], vec![1, 0], vec![1, 1]])]
+    #[case::with_empty_list(&[2, 0, 3], vec![])]
+    fn product_should_return_all_combinations(
+        #[case] lens: &[usize],
+        #[case] expected: Vec<Vec<usize>>,
+    ) {
+        assert_eq!(expected, combinations(MatrixMode::Product, lens));
+    }
+
+    #[test]
+    fn zip_should_take_the_same_index_in_every_list() {
+        assert_eq!(
+            vec![vec![0, 0, 0], vec![1, 1, 1]],
+            combinations(MatrixMode::Zip, &[2, 2, 2])
+        );
+    }
+
+    #[rstest]
+    #[case(&[2, 3])]
+    #[case(&[2, 2, 2])]
+    #[case(&[3, 3, 3, 3])]
+    #[case(&[6, 5, 4, 4])]
+    #[case(&[2, 7, 1, 3, 5])]
+    fn pairwise_should_cover_all_pairs(#[case] lens: &[usize]) {
+        let rows = combinations(MatrixMode::Pairwise, lens);
+
+        for i in 0..lens.len() {
+            for j in (i + 1)..lens.len() {
+                for a in 0..lens[i] {
+                    for b in 0..lens[j] {
+                        assert!(
+                            rows.iter().any(|r| r[i] == a && r[j] == b),
+                            "Pair ({}: {}, {}: {}) is not covered",
+                            i,
+                            a,
+                            j,
+                            b
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    #[rstest]
+    #[case(&[2, 2, 2], 4)]
+    #[case(&[3, 3, 3, 3], 12)]
+    #[case(&[6, 5, 4, 4], 35)]
+    fn pairwise_should_generate_less_tests_than_product(
+        #[case] lens: &[usize],
+        #[case] max: usize,
+    ) {
+        let rows = combinations(MatrixMode::Pairwise, lens);
+
+        assert!(rows.len() <= max, "Too many tests: {}", rows.len());
+        assert_eq!(
+            rows.len(),
+            rows.iter().collect::<std::collections::HashSet<_>>().len()
+        );
+    }
+
+    #[test]
+    fn pairwise_should_be_deterministic() {
+        assert_eq!(
+            combinations(MatrixMode::Pairwise, &[4, 3, 3, 2]),
+            combinations(MatrixMode::Pairwise, &[4, 3, 3, 2])
+        );
+    }
+}
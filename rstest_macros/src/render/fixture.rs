@@ -1,11 +1,12 @@
 use proc_macro2::{Span, TokenStream};
-use syn::{parse_quote, Ident, ItemFn, ReturnType};
+use syn::{parse_quote, spanned::Spanned, Expr, FnArg, Ident, ItemFn, ReturnType, WhereClause};
 
-use quote::quote;
+use quote::{quote, quote_spanned};
 
 use super::{inject, render_exec_call};
+use crate::refident::MaybeIdent;
 use crate::resolver::{self, Resolver};
-use crate::utils::{fn_args, fn_args_idents};
+use crate::utils::{fn_args, fn_args_idents, has_some_generics, type_has_some_impl};
 use crate::{parse::fixture::FixtureInfo, utils::generics_clean_up};
 
 fn wrap_return_type_as_static_ref(rt: ReturnType) -> ReturnType {
@@ -17,22 +18,249 @@ fn wrap_return_type_as_static_ref(rt: ReturnType) -> ReturnType {
     }
 }
 
-fn wrap_call_impl_with_call_once_impl(call_impl: TokenStream, rt: &ReturnType) -> TokenStream {
+fn wrap_return_type_as_shared(rt: ReturnType) -> ReturnType {
     match rt {
         syn::ReturnType::Type(_, t) => parse_quote! {
-            static mut S: Option<#t> = None;
-            static CELL: std::sync::Once = std::sync::Once::new();
-            CELL.call_once(|| unsafe { S = Some(#call_impl) });
-            unsafe { S.as_ref().unwrap() }
+           -> rstest::scope::Shared<#t>
         },
-        _ => parse_quote! {
-            static CELL: std::sync::Once = std::sync::Once::new();
-            CELL.call_once(|| #call_impl );
+        o => o,
+    }
+}
+
+/// Generic once and scoped fixtures store a value for each concrete type: the value type
+/// should be `Send + Sync + 'static` to be stored in a shared type map.
+fn where_clause_with_once_bounds(
+    where_clause: Option<&WhereClause>,
+    rt: &ReturnType,
+) -> Option<WhereClause> {
+    let mut where_clause = where_clause.cloned();
+    match rt {
+        syn::ReturnType::Type(_, t) if !type_has_some_impl(t) => {
+            where_clause
+                .get_or_insert_with(|| parse_quote! { where })
+                .predicates
+                .push(parse_quote! { #t: Send + Sync + 'static });
+        }
+        _ => {}
+    }
+    where_clause
+}
+
+/// Generic once fixtures store a value for each fixture instance: the key is composed by
+/// the generic types, the const parameters and the types of `impl` arguments, so all of
+/// them should be `'static`.
+fn add_static_bounds_to_generics(fixture: &mut ItemFn) {
+    struct StaticImpl;
+    impl syn::visit_mut::VisitMut for StaticImpl {
+        fn visit_type_impl_trait_mut(&mut self, i: &mut syn::TypeImplTrait) {
+            syn::visit_mut::visit_type_impl_trait_mut(self, i);
+            i.bounds.push(parse_quote! { 'static });
+        }
+    }
+    let type_params = fixture
+        .sig
+        .generics
+        .type_params()
+        .map(|tp| tp.ident.clone())
+        .collect::<Vec<_>>();
+    let where_clause = fixture.sig.generics.make_where_clause();
+    for tp in type_params {
+        where_clause.predicates.push(parse_quote! { #tp: 'static });
+    }
+    for arg in fixture.sig.inputs.iter_mut() {
+        if let FnArg::Typed(arg) = arg {
+            syn::visit_mut::VisitMut::visit_type_mut(&mut StaticImpl, arg.ty.as_mut());
+        }
+    }
+}
+
+/// The `rstest::once::TypeKey` that identify the instance of a generic once fixture.
+fn render_once_type_key(fixture: &ItemFn) -> TokenStream {
+    let types = fixture.sig.generics.type_params().map(|tp| &tp.ident);
+    let consts = fixture.sig.generics.const_params().map(|cp| &cp.ident);
+    let impl_args = fixture
+        .sig
+        .inputs
+        .iter()
+        .filter(|arg| matches!(arg, FnArg::Typed(arg) if type_has_some_impl(&arg.ty)))
+        .filter_map(MaybeIdent::maybe_ident);
+    quote! {
+        rstest::once::TypeKey::new()
+            #(.with::<#types>())*
+            #(.with_const(#consts as u128))*
+            #(.with_type_of(&#impl_args))*
+    }
+}
+
+fn wrap_call_impl_with_call_once_impl(
+    call_impl: TokenStream,
+    rt: &ReturnType,
+    is_async: bool,
+    type_key: Option<&TokenStream>,
+) -> TokenStream {
+    match (type_key, is_async) {
+        (Some(type_key), _) => {
+            wrap_call_impl_with_generic_call_once_impl(call_impl, rt, is_async, type_key)
+        }
+        (None, true) => wrap_call_impl_with_async_call_once_impl(call_impl, rt),
+        (None, false) => wrap_call_impl_with_sync_call_once_impl(call_impl, rt),
+    }
+}
+
+/// `#[once(drop)]` fixtures store the value in a `rstest::once::DropAtExit` and give to
+/// every test a `rstest::scope::Shared` copy of it: at exit the value is dropped only if no
+/// other copy is still alive (e.g. in a timed out test's thread).
+fn wrap_call_impl_with_drop_at_exit_call_once_impl(
+    call_impl: TokenStream,
+    rt: &ReturnType,
+    is_async: bool,
+    type_key: Option<&TokenStream>,
+) -> TokenStream {
+    match rt {
+        syn::ReturnType::Type(_, t) => {
+            let once = wrap_call_impl_with_call_once_impl(
+                quote! { rstest::once::drop_at_exit(#call_impl) },
+                &parse_quote! { -> rstest::once::DropAtExit<#t> },
+                is_async,
+                type_key,
+            );
+            quote! { { #once }.get() }
+        }
+        _ => wrap_call_impl_with_call_once_impl(call_impl, rt, is_async, type_key),
+    }
+}
+
+fn wrap_call_impl_with_sync_call_once_impl(call_impl: TokenStream, rt: &ReturnType) -> TokenStream {
+    match rt {
+        syn::ReturnType::Type(_, t) => {
+            let cell_type = quote_spanned! { t.span() => rstest::once::OnceCell<#t> };
+            quote! {
+                static CELL: #cell_type = rstest::once::OnceCell::new();
+                CELL.get_or_init(|| #call_impl )
+            }
+        }
+        _ => quote! {
+            static CELL: rstest::once::OnceCell<()> = rstest::once::OnceCell::new();
+            CELL.get_or_init(|| #call_impl );
+        },
+    }
+}
+
+/// `#[scope(module)]` fixtures share the value with all tests of the same `#[rstest]`
+/// function: the fixture struct itself is the key used to identify the value.
+fn wrap_call_impl_with_module_scope_impl(
+    call_impl: TokenStream,
+    rt: &ReturnType,
+    is_async: bool,
+) -> TokenStream {
+    match rt {
+        syn::ReturnType::Type(_, t) if is_async => quote_spanned! { t.span() =>
+            rstest::scope::get_or_init_async::<Self, _, _>(async move { #call_impl }).await
+        },
+        syn::ReturnType::Type(_, t) => quote_spanned! { t.span() =>
+            rstest::scope::get_or_init::<Self, _, _>(|| #call_impl )
+        },
+        _ => call_impl,
+    }
+}
+
+/// `#[once(reset)]` fixtures reset the value for every test that use it and prevent that
+/// two tests use any of them concurrently: the access guard is released by the test's teardown
+/// also when a timed out test forgets its fixtures' teardowns.
+fn wrap_call_impl_with_reset(call_impl: TokenStream, is_async: bool) -> TokenStream {
+    let acquire = if is_async {
+        quote! { rstest::once::acquire_reset_access_async().await }
+    } else {
+        quote! { rstest::once::acquire_reset_access() }
+    };
+    quote! {
+        let access = #acquire;
+        rstest::teardown::register_release(access);
+        let fixture = { #call_impl };
+        Self::reset(&fixture);
+        fixture
+    }
+}
+
+fn render_reset_impl(fixture: &ItemFn) -> Option<TokenStream> {
+    match &fixture.sig.output {
+        syn::ReturnType::Type(_, t) => {
+            let generics = generics_clean_up(
+                &fixture.sig.generics,
+                std::iter::empty(),
+                &fixture.sig.output,
+            );
+            let where_clause = &generics.where_clause;
+            Some(quote! {
+                pub fn reset #generics (fixture: &#t) #where_clause {
+                    rstest::once::Reset::reset(fixture)
+                }
+            })
+        }
+        _ => None,
+    }
+}
+
+fn wrap_call_impl_with_teardown(call_impl: TokenStream, teardown: &Expr) -> TokenStream {
+    quote! {
+        let fixture = #call_impl;
+        rstest::teardown::register(&fixture, #teardown);
+        fixture
+    }
+}
+
+fn wrap_call_impl_with_async_call_once_impl(
+    call_impl: TokenStream,
+    rt: &ReturnType,
+) -> TokenStream {
+    match rt {
+        syn::ReturnType::Type(_, t) => {
+            let cell_type = quote_spanned! { t.span() => rstest::once::AsyncOnceCell<#t> };
+            quote! {
+                static CELL: #cell_type = rstest::once::AsyncOnceCell::new();
+                CELL.get_or_init(async move { #call_impl }).await
+            }
+        }
+        _ => quote! {
+            static CELL: rstest::once::AsyncOnceCell<()> = rstest::once::AsyncOnceCell::new();
+            CELL.get_or_init(async move { #call_impl }).await;
         },
     }
 }
 
-pub(crate) fn render(fixture: ItemFn, info: FixtureInfo) -> TokenStream {
+fn wrap_call_impl_with_generic_call_once_impl(
+    call_impl: TokenStream,
+    rt: &ReturnType,
+    is_async: bool,
+    type_key: &TokenStream,
+) -> TokenStream {
+    let get_or_init = if is_async {
+        quote! { CELLS.get_or_init_async(#type_key, async move { #call_impl }).await }
+    } else {
+        quote! { CELLS.get_or_init(#type_key, || #call_impl ) }
+    };
+    let cells = quote! {
+        static CELLS: rstest::once::OnceTypeMap = rstest::once::OnceTypeMap::new();
+    };
+    match rt {
+        syn::ReturnType::Type(_, _) => quote! {
+            #cells
+            #get_or_init
+        },
+        _ => quote! {
+            #cells
+            #get_or_init;
+        },
+    }
+}
+
+pub(crate) fn render(mut fixture: ItemFn, info: FixtureInfo) -> TokenStream {
+    let type_key = if info.attributes.is_once() && has_some_generics(&fixture) {
+        add_static_bounds_to_generics(&mut fixture);
+        Some(render_once_type_key(&fixture))
+    } else {
+        None
+    };
     let name = &fixture.sig.ident;
     let asyncness = &fixture.sig.asyncness.clone();
     let vargs = fn_args_idents(&fixture).cloned().collect::<Vec<_>>();
@@ -46,8 +274,8 @@ pub(crate) fn render(fixture: ItemFn, info: FixtureInfo) -> TokenStream {
         .unwrap_or_else(|| fixture.sig.output.clone());
     let default_generics =
         generics_clean_up(&fixture.sig.generics, std::iter::empty(), &default_output);
-    let default_where_clause = &default_generics.where_clause;
-    let where_clause = &fixture.sig.generics.where_clause;
+    let mut default_where_clause = default_generics.where_clause.clone();
+    let mut where_clause = fixture.sig.generics.where_clause.clone();
     let mut output = fixture.sig.output.clone();
     let visibility = &fixture.vis;
     let resolver = (
@@ -67,9 +295,52 @@ pub(crate) fn render(fixture: ItemFn, info: FixtureInfo) -> TokenStream {
     let mut call_impl = render_exec_call(parse_quote! { #name }, args, asyncness.is_some());
 
     if info.attributes.is_once() {
-        call_impl = wrap_call_impl_with_call_once_impl(call_impl, &output);
-        output = wrap_return_type_as_static_ref(output);
-        default_output = wrap_return_type_as_static_ref(default_output);
+        if type_key.is_some() {
+            where_clause = where_clause_with_once_bounds(where_clause.as_ref(), &output);
+            default_where_clause =
+                where_clause_with_once_bounds(default_where_clause.as_ref(), &default_output);
+        }
+        call_impl = if info.attributes.is_once_drop() {
+            wrap_call_impl_with_drop_at_exit_call_once_impl(
+                call_impl,
+                &output,
+                asyncness.is_some(),
+                type_key.as_ref(),
+            )
+        } else {
+            wrap_call_impl_with_call_once_impl(
+                call_impl,
+                &output,
+                asyncness.is_some(),
+                type_key.as_ref(),
+            )
+        };
+        if info.attributes.is_once_drop() {
+            output = wrap_return_type_as_shared(output);
+            default_output = wrap_return_type_as_shared(default_output);
+        } else {
+            output = wrap_return_type_as_static_ref(output);
+            default_output = wrap_return_type_as_static_ref(default_output);
+        }
+    }
+    let reset = if info.attributes.is_once_reset() {
+        call_impl = wrap_call_impl_with_reset(call_impl, asyncness.is_some());
+        render_reset_impl(&fixture)
+    } else {
+        None
+    };
+    if info.attributes.is_module_scope() {
+        if has_some_generics(&fixture) {
+            where_clause = where_clause_with_once_bounds(where_clause.as_ref(), &output);
+            default_where_clause =
+                where_clause_with_once_bounds(default_where_clause.as_ref(), &default_output);
+        }
+        call_impl = wrap_call_impl_with_module_scope_impl(call_impl, &output, asyncness.is_some());
+        output = wrap_return_type_as_shared(output);
+        default_output = wrap_return_type_as_shared(default_output);
+    }
+    if let Some(teardown) = info.attributes.get_teardown() {
+        call_impl = wrap_call_impl_with_teardown(call_impl, teardown);
     }
 
     quote! {
@@ -88,6 +359,8 @@ pub(crate) fn render(fixture: ItemFn, info: FixtureInfo) -> TokenStream {
                 #call_get
             }
 
+            #reset
+
             #(#partials)*
         }
 
@@ -107,11 +380,21 @@ fn render_partial_impl(
         .extract_partial_type(n)
         .unwrap_or_else(|| fixture.sig.output.clone());
 
+    let mut generics = generics_clean_up(&fixture.sig.generics, fn_args(fixture).take(n), &output);
     if info.attributes.is_once() {
+        if has_some_generics(fixture) {
+            generics.where_clause =
+                where_clause_with_once_bounds(generics.where_clause.as_ref(), &output);
+        }
         output = wrap_return_type_as_static_ref(output);
     }
-
-    let generics = generics_clean_up(&fixture.sig.generics, fn_args(fixture).take(n), &output);
+    if info.attributes.is_module_scope() {
+        if has_some_generics(fixture) {
+            generics.where_clause =
+                where_clause_with_once_bounds(generics.where_clause.as_ref(), &output);
+        }
+        output = wrap_return_type_as_shared(output);
+    }
     let where_clause = &generics.where_clause;
     let asyncness = &fixture.sig.asyncness;
 
@@ -240,6 +523,278 @@ mod should {
         assert_eq!(signature.output, "-> &'static (u32, B, String, &str)".ast())
     }
 
+    #[test]
+    fn use_once_cell_if_once_fixture() {
+        let item_fn = parse_str::<ItemFn>(
+            r#"
+                pub fn test(v: u32) -> u32 { v }
+        "#,
+        )
+        .unwrap();
+        let info = FixtureInfo::default().with_once();
+
+        let out: FixtureOutput = parse2(render(item_fn.clone(), info)).unwrap();
+
+        let get = select_method(out.core_impl, "get").unwrap();
+        let expected: syn::Block = r#"{
+            static CELL: rstest::once::OnceCell<u32> = rstest::once::OnceCell::new();
+            CELL.get_or_init(|| test(v))
+        }"#
+        .ast();
+
+        assert_eq!(expected, get.block);
+    }
+
+    #[test]
+    fn return_a_shared_value_if_module_scope_fixture() {
+        let item_fn = parse_str::<ItemFn>(
+            r#"
+                pub fn test(v: u32) -> u32 { v }
+        "#,
+        )
+        .unwrap();
+        let info = FixtureInfo::default().with_module_scope();
+
+        let out: FixtureOutput = parse2(render(item_fn.clone(), info)).unwrap();
+
+        let get = select_method(out.core_impl.clone(), "get").unwrap();
+        let expected: syn::Block = r#"{
+            rstest::scope::get_or_init::<Self, _, _>(|| test(v))
+        }"#
+        .ast();
+
+        assert_eq!(get.sig.output, "-> rstest::scope::Shared<u32>".ast());
+        assert_eq!(expected, get.block);
+        for method in ["default", "partial_1"] {
+            let m = select_method(out.core_impl.clone(), method).unwrap();
+            assert_eq!(m.sig.output, "-> rstest::scope::Shared<u32>".ast());
+        }
+    }
+
+    #[test]
+    fn reset_value_and_acquire_access_if_once_reset_fixture() {
+        let item_fn = parse_str::<ItemFn>(
+            r#"
+                pub fn test<R: Default, A>(a: A) -> R { R::default() }
+        "#,
+        )
+        .unwrap();
+        let info = FixtureInfo::default().with_once_reset();
+
+        let out: FixtureOutput = parse2(render(item_fn.clone(), info)).unwrap();
+
+        let get = select_method(out.core_impl.clone(), "get").unwrap();
+        let expected: syn::Block = r#"{
+            let access = rstest::once::acquire_reset_access();
+            rstest::teardown::register_release(access);
+            let fixture = {
+                static CELLS: rstest::once::OnceTypeMap = rstest::once::OnceTypeMap::new();
+                CELLS.get_or_init(rstest::once::TypeKey::new().with::<R>().with::<A>(), || test(a))
+            };
+            Self::reset(&fixture);
+            fixture
+        }"#
+        .ast();
+        assert_eq!(expected, get.block);
+
+        let reset = select_method(out.core_impl, "reset").unwrap();
+        let expected: ItemFn = r#"
+            pub fn reset<R: Default>(fixture: &R) where R: 'static {
+                rstest::once::Reset::reset(fixture)
+            }
+        "#
+        .ast();
+        assert_eq!(expected.sig, reset.sig);
+        assert_eq!(*expected.block, reset.block);
+    }
+
+    #[test]
+    fn acquire_access_without_block_if_async_once_reset_fixture() {
+        let item_fn = parse_str::<ItemFn>(
+            r#"
+                pub async fn test(v: u32) -> u32 { v }
+        "#,
+        )
+        .unwrap();
+        let info = FixtureInfo::default().with_once_reset();
+
+        let out: FixtureOutput = parse2(render(item_fn.clone(), info)).unwrap();
+
+        let get = select_method(out.core_impl, "get").unwrap();
+        let expected: syn::Block = r#"{
+            let access = rstest::once::acquire_reset_access_async().await;
+            rstest::teardown::register_release(access);
+            let fixture = {
+                static CELL: rstest::once::AsyncOnceCell<u32> = rstest::once::AsyncOnceCell::new();
+                CELL.get_or_init(async move { test(v).await }).await
+            };
+            Self::reset(&fixture);
+            fixture
+        }"#
+        .ast();
+        assert_eq!(expected, get.block);
+    }
+
+    #[test]
+    fn use_async_once_cell_if_async_once_fixture() {
+        let item_fn = parse_str::<ItemFn>(
+            r#"
+                pub async fn test(v: u32) -> u32 { v }
+        "#,
+        )
+        .unwrap();
+        let info = FixtureInfo::default().with_once();
+
+        let out: FixtureOutput = parse2(render(item_fn.clone(), info)).unwrap();
+
+        let get = select_method(out.core_impl, "get").unwrap();
+        let expected: syn::Block = r#"{
+            static CELL: rstest::once::AsyncOnceCell<u32> = rstest::once::AsyncOnceCell::new();
+            CELL.get_or_init(async move { test(v).await }).await
+        }"#
+        .ast();
+
+        assert_eq!(get.sig.output, "-> &'static u32".ast());
+        assert_eq!(expected, get.block);
+    }
+
+    #[test]
+    fn register_value_to_drop_at_exit_if_once_drop_fixture() {
+        let item_fn = parse_str::<ItemFn>(
+            r#"
+                pub fn test(v: u32) -> u32 { v }
+        "#,
+        )
+        .unwrap();
+        let info = FixtureInfo::default().with_once_drop();
+
+        let out: FixtureOutput = parse2(render(item_fn.clone(), info)).unwrap();
+
+        let get = select_method(out.core_impl, "get").unwrap();
+        let expected: syn::Block = r#"{
+            {
+                static CELL: rstest::once::OnceCell<rstest::once::DropAtExit<u32>> =
+                    rstest::once::OnceCell::new();
+                CELL.get_or_init(|| rstest::once::drop_at_exit(test(v)))
+            }
+            .get()
+        }"#
+        .ast();
+
+        assert_eq!(get.sig.output, "-> rstest::scope::Shared<u32>".ast());
+        assert_eq!(expected, get.block);
+    }
+
+    #[test]
+    fn use_once_type_map_and_add_bounds_if_generic_once_fixture() {
+        let item_fn = parse_str::<ItemFn>(
+            r#"
+                pub fn test<R: Default>() -> R where R: Clone { R::default() }
+        "#,
+        )
+        .unwrap();
+        let info = FixtureInfo::default().with_once();
+
+        let out: FixtureOutput = parse2(render(item_fn.clone(), info)).unwrap();
+
+        let get = select_method(out.core_impl.clone(), "get").unwrap();
+        let expected: syn::Block = r#"{
+            static CELLS: rstest::once::OnceTypeMap = rstest::once::OnceTypeMap::new();
+            CELLS.get_or_init(rstest::once::TypeKey::new().with::<R>(), || test())
+        }"#
+        .ast();
+
+        assert_eq!(get.sig.output, "-> &'static R".ast());
+        assert_eq!(
+            get.sig.generics.where_clause,
+            Some("where R: Clone, R: 'static, R: Send + Sync + 'static".ast())
+        );
+        assert_eq!(expected, get.block);
+
+        let default = select_method(out.core_impl, "default").unwrap();
+        assert_eq!(
+            default.sig.generics.where_clause,
+            Some("where R: Clone, R: 'static, R: Send + Sync + 'static".ast())
+        );
+    }
+
+    #[test]
+    fn key_generic_once_fixture_by_all_generic_parameters() {
+        let item_fn = parse_str::<ItemFn>(
+            r#"
+                pub fn test<T: Debug, const N: usize>(v: T, w: impl Display) -> String {
+                    format!("{:?}{}{}", v, w, N)
+                }
+        "#,
+        )
+        .unwrap();
+        let info = FixtureInfo::default().with_once();
+
+        let out: FixtureOutput = parse2(render(item_fn.clone(), info)).unwrap();
+
+        let get = select_method(out.core_impl, "get").unwrap();
+        let expected: syn::Block = r#"{
+            static CELLS: rstest::once::OnceTypeMap = rstest::once::OnceTypeMap::new();
+            CELLS.get_or_init(
+                rstest::once::TypeKey::new()
+                    .with::<T>()
+                    .with_const(N as u128)
+                    .with_type_of(&w),
+                || test(v, w)
+            )
+        }"#
+        .ast();
+        assert_eq!(expected, get.block);
+        assert_eq!(
+            get.sig.generics.where_clause,
+            Some("where T: 'static, String: Send + Sync + 'static".ast())
+        );
+        assert_eq!(
+            get.sig.inputs.last().unwrap().clone(),
+            "w: impl Display + 'static".ast()
+        );
+    }
+
+    #[test]
+    fn not_add_bounds_to_impl_types_if_generic_once_fixture() {
+        let item_fn = parse_str::<ItemFn>(
+            r#"
+                pub fn test() -> impl AsRef<str> { "42" }
+        "#,
+        )
+        .unwrap();
+        let info = FixtureInfo::default().with_once();
+
+        let out: FixtureOutput = parse2(render(item_fn.clone(), info)).unwrap();
+
+        let get = select_method(out.core_impl, "get").unwrap();
+
+        assert!(get.sig.generics.where_clause.is_none());
+    }
+
+    #[test]
+    fn register_teardown_in_get_if_teardown_attribute() {
+        let item_fn = parse_str::<ItemFn>(
+            r#"
+                pub fn test(v: u32) -> u32 { v }
+        "#,
+        )
+        .unwrap();
+        let info = FixtureInfo::default().with_teardown("|v| move || drop(v)");
+
+        let out: FixtureOutput = parse2(render(item_fn.clone(), info)).unwrap();
+
+        let get = select_method(out.core_impl, "get").unwrap();
+        let expected: syn::Block = r#"{
+            let fixture = test(v);
+            rstest::teardown::register(&fixture, |v| move || drop(v));
+            fixture
+        }"#
+        .ast();
+
+        assert_eq!(expected, get.block);
+    }
+
     #[template]
     #[rstest(
         method => ["default", "get", "partial_1", "partial_2", "partial_3"])
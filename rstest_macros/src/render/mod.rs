@@ -1,3 +1,4 @@
+mod combinations;
 pub(crate) mod fixture;
 mod test;
 mod wrapper;
@@ -6,15 +7,17 @@ use std::collections::HashMap;
 use syn::token::Async;
 
 use proc_macro2::{Span, TokenStream};
-use syn::{parse_quote, Attribute, Expr, FnArg, Ident, ItemFn, Path, ReturnType, Stmt};
+use syn::{parse_quote, Attribute, Expr, FnArg, Ident, ItemFn, LitStr, Path, ReturnType, Stmt};
 
 use quote::{format_ident, quote};
 
-use crate::utils::attr_ends_with;
+use crate::utils::{attr_ends_with, attr_in, fn_arg_mutability, literal_name, sanitize_name};
 use crate::{
     parse::{
         rstest::{RsTestAttributes, RsTestData, RsTestInfo},
+        table::Table,
         testcase::TestCase,
+        timeout::{self, TimeoutMode},
         vlist::ValueList,
     },
     utils::attr_is,
@@ -52,6 +55,8 @@ pub(crate) fn single(mut test: ItemFn, info: RsTestInfo) -> TokenStream {
         resolver,
         &info.attributes,
         &generic_types,
+        None,
+        false,
     )
 }
 
@@ -59,56 +64,92 @@ pub(crate) fn parametrize(test: ItemFn, info: RsTestInfo) -> TokenStream {
     let RsTestInfo { data, attributes } = info;
     let resolver_fixtures = resolver::fixtures::get(data.fixtures());
 
-    let rendered_cases = cases_data(&data, test.sig.ident.span())
-        .map(|(name, attrs, resolver)| {
-            TestCaseRender::new(name, attrs, (resolver, &resolver_fixtures))
-        })
-        .map(|case| case.render(&test, &attributes))
-        .collect();
+    let mut rendered_cases: TokenStream =
+        cases_data(&data, test.sig.ident.span(), attributes.auto_names())
+            .map(|(name, case, resolver)| {
+                TestCaseRender::new(name, &case.attrs, (resolver, &resolver_fixtures))
+                    .with_description(case.text_description.as_ref())
+            })
+            .map(|case| case.render(&test, &attributes))
+            .collect();
+    rendered_cases.extend(data.tables().map(Table::track));
 
-    test_group(test, rendered_cases)
+    let size = data.cases().filter(|case| !is_ignored(&case.attrs)).count();
+    test_group(test, rendered_cases, size)
 }
 
 impl ValueList {
-    fn render(
-        &self,
-        test: &ItemFn,
-        resolver: &dyn Resolver,
-        attrs: &[syn::Attribute],
-        attributes: &RsTestAttributes,
-    ) -> TokenStream {
-        let span = test.sig.ident.span();
-        let test_cases = self
-            .argument_data(resolver)
-            .map(|(name, r)| TestCaseRender::new(Ident::new(&name, span), attrs, r))
-            .map(|test_case| test_case.render(test, attributes));
-
-        quote! { #(#test_cases)* }
-    }
-
     fn argument_data<'a>(
         &'a self,
         resolver: &'a dyn Resolver,
+        auto_names: bool,
     ) -> impl Iterator<Item = (String, Box<(&'a dyn Resolver, (String, Expr))>)> + 'a {
+        self.names(auto_names)
+            .into_iter()
+            .zip(self.values.iter())
+            .map(move |(name, value)| {
+                let resolver_this = (self.arg.to_string(), value.expr.clone());
+                (name, Box::new((resolver, resolver_this)))
+            })
+    }
+
+    /// The module names of the values: the value's label or the name derived from the
+    /// literal value in `auto_names` mode, otherwise the value's index. If some derived
+    /// names are not unique we fall back to use the index.
+    fn names(&self, auto_names: bool) -> Vec<String> {
         let max_len = self.values.len();
-        self.values.iter().enumerate().map(move |(index, expr)| {
-            let name = format!(
-                "{}_{:0len$}",
+        let by_index = |index: usize, description: &Option<String>| {
+            format!(
+                "{}_{:0len$}{d}",
                 self.arg,
                 index + 1,
-                len = max_len.display_len()
-            );
-            let resolver_this = (self.arg.to_string(), expr.clone());
-            (name, Box::new((resolver, resolver_this)))
-        })
+                len = max_len.display_len(),
+                d = description
+                    .as_ref()
+                    .map(|d| format!("_{}", d))
+                    .unwrap_or_default()
+            )
+        };
+        let names = self
+            .values
+            .iter()
+            .enumerate()
+            .map(|(index, value)| {
+                let derived = value.label.as_ref().map(ToString::to_string).or_else(|| {
+                    Some(&value.expr)
+                        .filter(|_| auto_names)
+                        .and_then(literal_name)
+                });
+                match derived {
+                    Some(name) => format!("{}_{}", self.arg, name),
+                    None => by_index(index, &value.description),
+                }
+            })
+            .collect::<Vec<_>>();
+        let unique = names.iter().collect::<std::collections::HashSet<_>>();
+        if !auto_names || unique.len() == names.len() {
+            return names;
+        }
+        self.values
+            .iter()
+            .enumerate()
+            .map(|(index, value)| match value.label {
+                Some(ref label) => format!("{}_{}", self.arg, label),
+                None => by_index(index, &value.description),
+            })
+            .collect()
     }
 }
 
+/// Render the tests for the given values indexes `combinations`: the combinations must be
+/// sorted because all tests that share the first value are rendered in the same module.
 fn _matrix_recursive<'a>(
     test: &ItemFn,
     list_values: &'a [&'a ValueList],
+    combinations: &[&[usize]],
     resolver: &dyn Resolver,
     attrs: &'a [syn::Attribute],
+    description: Option<&'a LitStr>,
     attributes: &RsTestAttributes,
 ) -> TokenStream {
     if list_values.is_empty() {
@@ -117,17 +158,40 @@ fn _matrix_recursive<'a>(
     let vlist = list_values[0];
     let list_values = &list_values[1..];
 
-    if list_values.is_empty() {
-        vlist.render(test, resolver, attrs, attributes)
-    } else {
-        let span = test.sig.ident.span();
-        let modules = vlist.argument_data(resolver).map(move |(name, resolver)| {
-            _matrix_recursive(test, list_values, &resolver, attrs, attributes)
-                .wrap_by_mod(&Ident::new(&name, span))
-        });
-
-        quote! { #(#modules)* }
+    let span = test.sig.ident.span();
+    let mut groups: Vec<(usize, Vec<&[usize]>)> = vec![];
+    for combination in combinations {
+        match groups.last_mut() {
+            Some((index, tails)) if *index == combination[0] => tails.push(&combination[1..]),
+            _ => groups.push((combination[0], vec![&combination[1..]])),
+        }
     }
+    let mut data = vlist
+        .argument_data(resolver, attributes.auto_names())
+        .map(Some)
+        .collect::<Vec<_>>();
+    let rendered = groups.into_iter().filter_map(|(index, tails)| {
+        let (name, resolver) = data[index].take()?;
+        let name = Ident::new(&name, span);
+        Some(if list_values.is_empty() {
+            TestCaseRender::new(name, attrs, resolver)
+                .with_description(description)
+                .render(test, attributes)
+        } else {
+            _matrix_recursive(
+                test,
+                list_values,
+                &tails,
+                &resolver,
+                attrs,
+                description,
+                attributes,
+            )
+            .wrap_by_mod(&name)
+        })
+    });
+
+    quote! { #(#rendered)* }
 }
 
 pub(crate) fn matrix(test: ItemFn, info: RsTestInfo) -> TokenStream {
@@ -136,30 +200,84 @@ pub(crate) fn matrix(test: ItemFn, info: RsTestInfo) -> TokenStream {
     } = info;
     let span = test.sig.ident.span();
 
-    let cases = cases_data(&data, span).collect::<Vec<_>>();
+    let cases = cases_data(&data, span, attributes.auto_names()).collect::<Vec<_>>();
+    let mode = attributes.matrix_mode();
+    let exclusions = data.exclusions().collect::<Vec<_>>();
+    let combinations = |list_values: &[&ValueList]| {
+        let lens = list_values
+            .iter()
+            .map(|vlist| vlist.values.len())
+            .collect::<Vec<_>>();
+        let mut combinations = combinations::combinations(mode, &lens);
+        combinations.retain(|combination| {
+            let value_of = |arg: &Ident| {
+                list_values
+                    .iter()
+                    .position(|vlist| &vlist.arg == arg)
+                    .map(|pos| &list_values[pos].values[combination[pos]])
+            };
+            !exclusions
+                .iter()
+                .any(|exclusion| exclusion.matches(value_of))
+        });
+        combinations
+    };
+    let render = |list_values: &[&ValueList],
+                  resolver: &dyn Resolver,
+                  attrs: &[syn::Attribute],
+                  description: Option<&LitStr>| {
+        let combinations = combinations(list_values);
+        let combinations = combinations.iter().map(Vec::as_slice).collect::<Vec<_>>();
+        (
+            _matrix_recursive(
+                &test,
+                list_values,
+                &combinations,
+                resolver,
+                attrs,
+                description,
+                &attributes,
+            ),
+            if is_ignored(attrs) {
+                0
+            } else {
+                combinations.len()
+            },
+        )
+    };
 
     let resolver = resolver::fixtures::get(data.fixtures());
-    let rendered_cases = if cases.is_empty() {
+    let (mut rendered_cases, size) = if cases.is_empty() {
         let list_values = data.list_values().collect::<Vec<_>>();
-        _matrix_recursive(&test, &list_values, &resolver, &[], &attributes)
+        render(&list_values, &resolver, &[], None)
     } else {
         cases
             .into_iter()
-            .map(|(case_name, attrs, case_resolver)| {
-                let list_values = data.list_values().collect::<Vec<_>>();
-                _matrix_recursive(
-                    &test,
+            .map(|(case_name, case, case_resolver)| {
+                let list_values = data
+                    .list_values()
+                    .map(|vlist| case.values_override(&vlist.arg).unwrap_or(vlist))
+                    .collect::<Vec<_>>();
+                let (rendered, case_size) = render(
                     &list_values,
                     &(case_resolver, &resolver),
-                    attrs,
-                    &attributes,
-                )
-                .wrap_by_mod(&case_name)
+                    &case.attrs,
+                    case.text_description.as_ref(),
+                );
+                (rendered.wrap_by_mod(&case_name), case_size)
             })
-            .collect()
+            .fold(
+                (TokenStream::new(), 0),
+                |(mut all, size), (rendered, case_size)| {
+                    all.extend(rendered);
+                    (all, size + case_size)
+                },
+            )
     };
 
-    test_group(test, rendered_cases)
+    rendered_cases.extend(data.tables().map(Table::track));
+
+    test_group(test, rendered_cases, size)
 }
 
 fn resolve_default_test_attr(is_async: bool) -> TokenStream {
@@ -178,22 +296,102 @@ fn render_exec_call(fn_path: Path, args: &[Ident], is_async: bool) -> TokenStrea
     }
 }
 
+/// The timeout that a test should use.
+enum Timeout {
+    /// The test's `#[timeout(expr)]`
+    Explicit(Box<Expr>),
+    /// The test's `#[timeout(expr, mode = watchdog)]`
+    Watchdog(Box<Expr>),
+    /// The runtime default timeout (`RSTEST_TIMEOUT`), if any
+    Default,
+    /// Opt out by `#[no_timeout]`
+    Disabled,
+}
+
+impl Timeout {
+    /// The last `#[timeout]` or `#[no_timeout]` attribute wins: so a case can override
+    /// the test's one.
+    fn from_attrs(attrs: Vec<Attribute>) -> syn::Result<Self> {
+        Ok(match attrs.last() {
+            Some(attr) if attr_is(attr, "no_timeout") => Self::Disabled,
+            Some(attr) => {
+                let timeout = attr.parse_args::<timeout::Timeout>()?;
+                match timeout.mode {
+                    TimeoutMode::Thread => Self::Explicit(Box::new(timeout.duration)),
+                    TimeoutMode::Watchdog => Self::Watchdog(Box::new(timeout.duration)),
+                }
+            }
+            None => Self::Default,
+        })
+    }
+
+    /// Sync tests with a watchdog or the default timeout are executed all in a worker
+    /// thread, fixtures resolution included: return the timeout to use inside the worker
+    /// and the worker to use, if any.
+    fn with_worker(self, is_async: bool) -> (Self, Option<Worker>) {
+        match (self, is_async) {
+            (Self::Watchdog(to_expr), false) => (Self::Disabled, Some(Worker::Watchdog(to_expr))),
+            (Self::Default, false) => (Self::Disabled, Some(Worker::Default)),
+            (timeout, _) => (timeout, None),
+        }
+    }
+}
+
+/// The worker thread that execute a whole sync test.
+enum Worker {
+    Watchdog(Box<Expr>),
+    Default,
+}
+
+impl Worker {
+    /// Wrap the test's `body` to execute it in the worker: the test output is wrapped in
+    /// a `rstest::timeout::TestOutput` too.
+    fn render(self, body: TokenStream, output: &ReturnType) -> (TokenStream, ReturnType) {
+        let execute = match self {
+            Self::Watchdog(to_expr) => quote! {
+                rstest::timeout::execute_with_watchdog(move || { #body }, #to_expr)
+            },
+            Self::Default => quote! {
+                rstest::timeout::execute_with_default_timeout_sync(move || { #body })
+            },
+        };
+        match output {
+            ReturnType::Default => (quote! { #execute; }, ReturnType::Default),
+            ReturnType::Type(_, t) => {
+                (execute, parse_quote! { -> rstest::timeout::TestOutput<#t> })
+            }
+        }
+    }
+}
+
 fn render_test_call(
     fn_path: Path,
     args: &[Ident],
-    timeout: Option<Expr>,
+    timeout: Timeout,
     is_async: bool,
 ) -> TokenStream {
     match (timeout, is_async) {
-        (Some(to_expr), true) => quote! {
+        (Timeout::Explicit(to_expr), true) => quote! {
             use rstest::timeout::*;
             execute_with_timeout_async(move || #fn_path(#(#args),*), #to_expr).await
         },
-        (Some(to_expr), false) => quote! {
+        (Timeout::Explicit(to_expr), false) => quote! {
             use rstest::timeout::*;
             execute_with_timeout_sync(move || #fn_path(#(#args),*), #to_expr)
         },
-        _ => render_exec_call(fn_path, args, is_async),
+        (Timeout::Default, true) if cfg!(feature = "async-timeout") => quote! {
+            rstest::timeout::execute_with_default_timeout_async(move || #fn_path(#(#args),*)).await
+        },
+        (Timeout::Watchdog(to_expr), true) => syn::Error::new_spanned(
+            to_expr,
+            "#[timeout(..., mode = watchdog)] cannot be used in async tests",
+        )
+        .to_compile_error(),
+        // Sync tests with watchdog or default timeout are executed in a worker by
+        // `Worker::render()`
+        (Timeout::Watchdog(_), false) | (Timeout::Default, _) | (Timeout::Disabled, _) => {
+            render_exec_call(fn_path, args, is_async)
+        }
     }
 }
 
@@ -209,6 +407,9 @@ fn render_test_call(
 /// * `resolver` - The resolver used to resolve injected values
 /// * `attributes` - Test attributes to select test behaviour
 /// * `generic_types` - The genrics type used in signature
+/// * `description` - The free text case description to show in the test banner
+/// * `in_test_group` - If the test is rendered in a module that define the `#[rstest]`
+///   tests group used by `#[scope(module)]` fixtures
 ///
 // Ok I need some refactoring here but now that not a real issue
 #[allow(clippy::too_many_arguments)]
@@ -223,6 +424,8 @@ fn single_test_case<'a>(
     resolver: impl Resolver,
     attributes: &'a RsTestAttributes,
     generic_types: &[Ident],
+    description: Option<&LitStr>,
+    in_test_group: bool,
 ) -> TokenStream {
     let (attrs, trace_me): (Vec<_>, Vec<_>) =
         attrs.iter().cloned().partition(|a| !attr_is(a, "trace"));
@@ -230,22 +433,43 @@ fn single_test_case<'a>(
     if !trace_me.is_empty() {
         attributes.add_trace(format_ident!("trace"));
     }
-    let inject = inject::resolve_aruments(args.iter(), &resolver, generic_types);
+    let strategies = attributes.strategies().collect::<HashMap<_, _>>();
+    let (generated, injected): (Vec<_>, Vec<_>) = args.iter().partition(|arg| {
+        arg.maybe_ident()
+            .map(|id| strategies.contains_key(id))
+            .unwrap_or_default()
+    });
+    let inject = inject::resolve_aruments(injected.into_iter(), &resolver, generic_types);
+    let seeded = args
+        .iter()
+        .filter(|arg| is_test_rng(arg))
+        .filter_map(MaybeIdent::maybe_ident)
+        .cloned()
+        .collect::<Vec<_>>();
     let args = args
         .iter()
         .filter_map(MaybeIdent::maybe_ident)
         .cloned()
         .collect::<Vec<_>>();
-    let trace_args = trace_arguments(args.iter(), &attributes);
+    let trace_args = trace_arguments(args.iter(), &attributes, &seeded);
 
     let is_async = asyncness.is_some();
-    let (attrs, timeouts): (Vec<_>, Vec<_>) =
-        attrs.iter().cloned().partition(|a| !attr_is(a, "timeout"));
-
-    let timeout = timeouts
-        .into_iter()
-        .last()
-        .map(|attribute| attribute.parse_args::<Expr>().unwrap());
+    let (attrs, timeouts): (Vec<_>, Vec<_>) = attrs
+        .iter()
+        .cloned()
+        .partition(|a| !attr_in(a, &["timeout", "no_timeout"]));
+    // Like timeout the last one wins: so a case can override the test's one
+    let (attrs, retries): (Vec<_>, Vec<_>) =
+        attrs.into_iter().partition(|a| !attr_is(a, "retries"));
+    // Already checked by parse: just report the errors if something went wrong
+    let (timeout, retries) = match (
+        Timeout::from_attrs(timeouts),
+        retries.last().map(crate::parse::parse_retries).transpose(),
+    ) {
+        (Ok(timeout), Ok(retries)) => (timeout, retries),
+        (Err(e), _) | (_, Err(e)) => return e.to_compile_error(),
+    };
+    let (timeout, worker) = timeout.with_worker(is_async);
 
     // If no injected attribut provided use the default one
     let test_attr = if attrs
@@ -257,31 +481,106 @@ fn single_test_case<'a>(
         Some(resolve_default_test_attr(is_async))
     };
     let execute = render_test_call(testfn_name.clone().into(), &args, timeout, is_async);
+    let test_start = match description {
+        Some(description) => quote! {
+            println!("{:-^40}", concat!(" TEST START: ", #description, " "));
+        },
+        None => quote! { println!("{:-^40}", " TEST START "); },
+    };
+    let body = if generated.is_empty() {
+        quote! {
+            let _teardown_scope = rstest::teardown::TearDownScope::new();
+            #inject
+            #trace_args
+            #test_start
+            #execute
+        }
+    } else {
+        // Every iteration resolves its own fixtures
+        let values = generated.iter().map(|arg| {
+            let mutability = fn_arg_mutability(arg);
+            let ident = arg.maybe_ident();
+            quote! { #mutability #ident }
+        });
+        let strategies = generated
+            .iter()
+            .filter_map(|arg| arg.maybe_ident().and_then(|id| strategies.get(id)));
+        quote! {
+            #test_start
+            rstest::strategy::check(
+                concat!(module_path!(), "::", stringify!(#name)),
+                (#(#strategies,)*),
+                |(#(#values,)*)| {
+                    let _teardown_scope = rstest::teardown::TearDownScope::new();
+                    #inject
+                    #trace_args
+                    #execute
+                },
+            )
+        }
+    };
+    let (body, output) = match worker {
+        Some(worker) => worker.render(body, output),
+        None => (body, output.clone()),
+    };
+    // Every attempt resolves its own fixtures
+    let body = match retries {
+        Some(retries) if is_async => quote! {
+            rstest::retry::execute_with_retries_async(#retries, || async { #body }).await
+        },
+        Some(retries) => quote! {
+            rstest::retry::execute_with_retries(#retries, || { #body })
+        },
+        None => body,
+    };
+    let enter_test_group = if in_test_group {
+        Some(quote! { let _test_group = RSTEST_TEST_GROUP.enter(); })
+    } else {
+        None
+    };
 
     quote! {
         #test_attr
         #(#attrs)*
         #asyncness fn #name() #output {
             #test_impl
-            #inject
-            #trace_args
-            println!("{:-^40}", " TEST START ");
-            #execute
+            #enter_test_group
+            let _test_context = rstest::context::TestContext::enter(
+                concat!(module_path!(), "::", stringify!(#name))
+            );
+            #body
         }
     }
 }
 
+/// `TestRng` arguments are traced also if the test is not: so a failing test always shows
+/// the seed that reproduces it.
+fn is_test_rng(arg: &FnArg) -> bool {
+    match arg {
+        FnArg::Typed(arg) => matches!(arg.ty.as_ref(), syn::Type::Path(p)
+            if p.path.segments.last().map(|s| s.ident == "TestRng").unwrap_or_default()),
+        _ => false,
+    }
+}
+
 fn trace_arguments<'a>(
     args: impl Iterator<Item = &'a Ident>,
     attributes: &RsTestAttributes,
+    seeded: &[Ident],
 ) -> Option<TokenStream> {
     let mut statements = args
-        .filter(|&arg| attributes.trace_me(arg))
+        .filter(|&arg| {
+            attributes.trace_me(arg) || (seeded.contains(arg) && !attributes.is_notraced(arg))
+        })
         .map(|arg| {
             let s: Stmt = parse_quote! {
                 println!("{} = {:?}", stringify!(#arg), #arg);
             };
-            s
+            // Keep the value also for the timeout report
+            let context: Stmt = parse_quote! {
+                rstest::context::TestContext::trace(stringify!(#arg), &#arg);
+            };
+            quote! { #s #context }
         })
         .peekable();
     if statements.peek().is_some() {
@@ -297,6 +596,7 @@ fn trace_arguments<'a>(
 struct TestCaseRender<'a> {
     name: Ident,
     attrs: &'a [syn::Attribute],
+    description: Option<&'a LitStr>,
     resolver: Box<dyn Resolver + 'a>,
 }
 
@@ -305,10 +605,16 @@ impl<'a> TestCaseRender<'a> {
         TestCaseRender {
             name,
             attrs,
+            description: None,
             resolver: Box::new(resolver),
         }
     }
 
+    fn with_description(mut self, description: Option<&'a LitStr>) -> Self {
+        self.description = description;
+        self
+    }
+
     fn render(self, testfn: &ItemFn, attributes: &RsTestAttributes) -> TokenStream {
         let args = testfn.sig.inputs.iter().cloned().collect::<Vec<_>>();
         let mut attrs = testfn.attrs.clone();
@@ -333,11 +639,19 @@ impl<'a> TestCaseRender<'a> {
             self.resolver,
             attributes,
             &generic_types,
+            self.description,
+            true,
         )
     }
 }
 
-fn test_group(mut test: ItemFn, rendered_cases: TokenStream) -> TokenStream {
+/// Ignored tests never enter in the group (when they run anyway the scoped values are
+/// released at exit).
+fn is_ignored(attrs: &[syn::Attribute]) -> bool {
+    attrs.iter().any(|attr| attr.path.is_ident("ignore"))
+}
+
+fn test_group(mut test: ItemFn, rendered_cases: TokenStream, size: usize) -> TokenStream {
     let fname = &test.sig.ident;
     test.attrs = vec![];
 
@@ -349,6 +663,9 @@ fn test_group(mut test: ItemFn, rendered_cases: TokenStream) -> TokenStream {
         mod #fname {
             use super::*;
 
+            static RSTEST_TEST_GROUP: rstest::scope::TestGroup =
+                rstest::scope::TestGroup::new(#size);
+
             #rendered_cases
         }
     }
@@ -364,10 +681,22 @@ impl<D: std::fmt::Display> DisplayLen for D {
     }
 }
 
-fn format_case_name(case: &TestCase, index: usize, display_len: usize) -> String {
+fn format_case_name(case: &TestCase, index: usize, display_len: usize, auto_names: bool) -> String {
     let description = case
         .description
         .as_ref()
+        .map(|d| d.to_string())
+        .or_else(|| {
+            case.text_description
+                .as_ref()
+                .map(|t| sanitize_name(&t.value()))
+                .filter(|t| !t.is_empty())
+        })
+        .or_else(|| {
+            Some(case)
+                .filter(|_| auto_names)
+                .and_then(case_literals_name)
+        })
         .map(|d| format!("_{}", d))
         .unwrap_or_default();
     format!(
@@ -378,10 +707,21 @@ fn format_case_name(case: &TestCase, index: usize, display_len: usize) -> String
     )
 }
 
+/// The name derived from the case's literal arguments
+fn case_literals_name(case: &TestCase) -> Option<String> {
+    let names = case
+        .args
+        .iter()
+        .filter_map(literal_name)
+        .collect::<Vec<_>>();
+    Some(sanitize_name(&names.join("_"))).filter(|n| !n.is_empty())
+}
+
 fn cases_data(
     data: &RsTestData,
     name_span: Span,
-) -> impl Iterator<Item = (Ident, &[syn::Attribute], HashMap<String, &syn::Expr>)> {
+    auto_names: bool,
+) -> impl Iterator<Item = (Ident, &TestCase, HashMap<String, &syn::Expr>)> {
     let display_len = data.cases().count().display_len();
     data.cases().enumerate().map({
         move |(n, case)| {
@@ -391,8 +731,11 @@ fn cases_data(
                 .zip(case.args.iter())
                 .collect::<HashMap<_, _>>();
             (
-                Ident::new(&format_case_name(case, n + 1, display_len), name_span),
-                case.attrs.as_slice(),
+                Ident::new(
+                    &format_case_name(case, n + 1, display_len, auto_names),
+                    name_span,
+                ),
+                case,
                 resolver_case,
             )
         }
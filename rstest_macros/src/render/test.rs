@@ -7,6 +7,8 @@ use syn::{
     ItemFn, ItemMod,
 };
 
+use quote::ToTokens;
+
 use super::*;
 use crate::test::{assert_eq, fixture, *};
 use crate::utils::*;
@@ -46,6 +48,19 @@ mod single_test_should {
 
         let result: ItemFn = single(input_fn.clone(), Default::default()).ast();
 
+        assert_eq!(
+            result.sig.output,
+            "-> rstest::timeout::TestOutput<Result<i32, String> >".ast()
+        );
+    }
+
+    #[test]
+    fn keep_return_type_if_not_executed_in_a_worker() {
+        let input_fn: ItemFn =
+            "#[no_timeout] fn function(fix: String) -> Result<i32, String> { Ok(42) }".ast();
+
+        let result: ItemFn = single(input_fn.clone(), Default::default()).ast();
+
         assert_eq!(result.sig.output, input_fn.sig.output);
     }
 
@@ -136,6 +151,18 @@ mod single_test_should {
         );
     }
 
+    #[test]
+    fn keep_traced_values_for_the_timeout_report() {
+        let input_fn: ItemFn = r#"#[trace]fn test(s: String) {} "#.ast();
+        let expected: Stmt = parse_quote! {
+            rstest::context::TestContext::trace(stringify!(s), &s);
+        };
+
+        let item_fn: ItemFn = single(input_fn.clone(), Default::default()).ast();
+
+        assert_in!(item_fn.block.display_code(), expected.display_code());
+    }
+
     #[test]
     fn trace_not_all_arguments_values() {
         let input_fn: ItemFn =
@@ -172,6 +199,130 @@ mod single_test_should {
         );
     }
 
+    #[rstest]
+    #[case::default("", "execute_with_default_timeout_sync")]
+    #[case::explicit("#[timeout(ms(10))]", "execute_with_timeout_sync")]
+    #[case::opt_out("#[no_timeout]", "")]
+    #[case::last_wins("#[timeout(ms(10))] #[no_timeout]", "")]
+    #[case::case_override("#[no_timeout] #[timeout(ms(10))]", "execute_with_timeout_sync")]
+    #[case::thread("#[timeout(ms(10), mode = thread)]", "execute_with_timeout_sync")]
+    #[case::watchdog("#[timeout(ms(10), mode = watchdog)]", "execute_with_watchdog")]
+    fn use_the_right_timeout(#[case] attributes: &str, #[case] expected: &str) {
+        let mut input_fn: ItemFn = r#"fn test(a: u32) {} "#.ast();
+        input_fn.attrs = attrs(attributes);
+
+        let result: ItemFn = single(input_fn.clone(), Default::default()).ast();
+        let code = result.block.display_code();
+
+        for executor in &[
+            "execute_with_default_timeout_sync",
+            "execute_with_timeout_sync",
+            "execute_with_watchdog",
+        ] {
+            assert_eq!(
+                &expected == executor,
+                code.contains(executor),
+                "{}",
+                executor
+            );
+        }
+        assert!(result
+            .attrs
+            .iter()
+            .all(|a| !attr_in(a, &["timeout", "no_timeout"])));
+    }
+
+    #[rstest]
+    #[case::none("fn test(a: u32) {}", None)]
+    #[case::sync("#[retries(3)] fn test(a: u32) {}", Some("execute_with_retries"))]
+    #[case::last_wins(
+        "#[retries(1)] #[retries(3)] fn test(a: u32) {}",
+        Some("execute_with_retries")
+    )]
+    #[case::async_test(
+        "#[retries(3)] async fn test(a: u32) {}",
+        Some("execute_with_retries_async")
+    )]
+    fn retry_the_whole_test(#[case] input: &str, #[case] expected: Option<&str>) {
+        let input_fn: ItemFn = input.ast();
+
+        let result: ItemFn = single(input_fn.clone(), Default::default()).ast();
+        let code = result.block.display_code();
+
+        match expected {
+            Some(executor) => {
+                assert_in!(code, executor);
+                assert_in!(code, "3u32");
+            }
+            None => assert_not_in!(code, "execute_with_retries"),
+        }
+        assert!(result.attrs.iter().all(|a| !attr_is(a, "retries")));
+    }
+
+    #[test]
+    fn enter_the_test_context() {
+        let input_fn: ItemFn = r#"fn test(a: u32) {} "#.ast();
+
+        let item_fn: ItemFn = single(input_fn.clone(), Default::default()).ast();
+
+        assert_in!(
+            item_fn.block.display_code(),
+            "rstest :: context :: TestContext :: enter"
+        );
+    }
+
+    #[rstest]
+    #[case::not_traced(vec![], true)]
+    #[case::notrace(vec!["rng"], false)]
+    fn trace_test_rng_arguments_also_if_not_traced(
+        #[case] notraces: Vec<&str>,
+        #[case] traced: bool,
+    ) {
+        let input_fn: ItemFn = r#"fn test(rng: rstest::TestRng, a: u32) {} "#.ast();
+        let mut attributes = RsTestAttributes::default();
+        attributes.add_notraces(notraces.into_iter().map(ident).collect());
+
+        let item_fn: ItemFn = single(
+            input_fn.clone(),
+            RsTestInfo {
+                attributes,
+                ..Default::default()
+            },
+        )
+        .ast();
+        let code = item_fn.block.display_code();
+
+        assert_eq!(traced, code.contains(&trace_argument_code_string("rng")));
+        assert_not_in!(code, trace_argument_code_string("a"));
+    }
+
+    #[test]
+    fn generate_strategy_arguments_and_inject_the_others() {
+        let input_fn: ItemFn = r#"fn test(a: u32, fix: String, mut b: i32) {} "#.ast();
+        let mut attributes = RsTestAttributes::default();
+        attributes.add_strategies(vec![
+            (ident("b"), expr("-5..5")),
+            (ident("a"), expr("0..10")),
+        ]);
+
+        let item_fn: ItemFn = single(
+            input_fn.clone(),
+            RsTestInfo {
+                attributes,
+                ..Default::default()
+            },
+        )
+        .ast();
+        let code = item_fn.block.display_code();
+
+        assert_in!(code, "rstest :: strategy :: check");
+        assert_in!(code, "(0 .. 10 , - 5 .. 5 ,)");
+        assert_in!(code, "| (a , mut b ,) |");
+        assert_in!(code, "let fix = fix :: default ()");
+        assert_not_in!(code, "let a =");
+        assert_not_in!(code, "let mut b =");
+    }
+
     #[rstest]
     #[case::sync("", parse_quote! { #[test] })]
     #[case::async_fn("async", parse_quote! { #[async_std::test] })]
@@ -356,6 +507,20 @@ impl ModuleInspector for TestsGroup {
     }
 }
 
+impl TestsGroup {
+    /// The expression that define the tests group used by `#[scope(module)]` fixtures
+    fn test_group_definition(&self) -> Option<String> {
+        self.module.content.as_ref().and_then(|(_, items)| {
+            items.iter().find_map(|it| match it {
+                syn::Item::Static(s) if s.ident == "RSTEST_TEST_GROUP" => {
+                    Some(s.expr.to_token_stream().to_string())
+                }
+                _ => None,
+            })
+        })
+    }
+}
+
 #[derive(Default, Debug)]
 struct Assignments(HashMap<String, syn::Expr>);
 
@@ -522,6 +687,42 @@ mod cases_should {
         }
     }
 
+    #[test]
+    fn define_a_test_group_that_contains_all_cases() {
+        let (item_fn, info) = some_simple_cases(3);
+
+        let tokens = parametrize(item_fn, info);
+
+        let output = TestsGroup::from(tokens);
+
+        assert_eq!(
+            output.test_group_definition().unwrap(),
+            quote! { rstest::scope::TestGroup::new(3usize) }.to_string()
+        );
+        for t in output.get_all_tests() {
+            assert_in!(
+                t.block.to_token_stream().to_string(),
+                quote! { let _test_group = RSTEST_TEST_GROUP.enter(); }.to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn not_count_ignored_cases_in_the_test_group() {
+        let (item_fn, info) = TestCaseBuilder::from(r#"fn test(v: i32){}"#)
+            .push_case(TestCase::from("1"))
+            .push_case(TestCase::from("2").with_attrs(attrs("#[ignore]")))
+            .push_case(TestCase::from("3"))
+            .take();
+
+        let tokens = parametrize(item_fn, info);
+
+        assert_eq!(
+            TestsGroup::from(tokens).test_group_definition().unwrap(),
+            quote! { rstest::scope::TestGroup::new(2usize) }.to_string()
+        );
+    }
+
     #[rstest]
     #[case::empty("")]
     #[case::some_attrs("#[a]#[b::c]#[should_panic]")]
@@ -599,7 +800,10 @@ mod cases_should {
 
         let tests = TestsGroup::from(tokens).get_all_tests();
 
-        assert_eq!(tests[0].sig.output, item_fn.sig.output);
+        assert_eq!(
+            tests[0].sig.output,
+            "-> rstest::timeout::TestOutput<Result<i32, String> >".ast()
+        );
     }
 
     #[test]
@@ -699,6 +903,48 @@ mod cases_should {
             .ends_with(&format!("_{}", description)));
     }
 
+    #[test]
+    fn use_sanitized_text_description_and_show_it_in_banner() {
+        let (item_fn, mut info) = one_simple_case();
+
+        if let &mut RsTestItem::TestCase(ref mut case) = &mut info.data.items[1] {
+            case.text_description = Some(parse_quote! { "http 1.1 keep-alive" });
+        } else {
+            panic!("Test case should be the second one");
+        }
+
+        let tokens = parametrize(item_fn, info);
+
+        let tests = TestsGroup::from(tokens).get_all_tests();
+
+        assert_eq!("case_1_http_1_1_keep_alive", tests[0].sig.ident.to_string());
+        assert_in!(
+            tests[0].block.to_token_stream().to_string(),
+            r#"concat ! (" TEST START: " , "http 1.1 keep-alive" , " ")"#
+        );
+    }
+
+    #[test]
+    fn derive_names_from_literal_arguments_in_auto_names_mode() {
+        let (item_fn, mut info) =
+            TestCaseBuilder::from(r#"fn test(a: u32, b: String) { println!("user code") }"#)
+                .push_case(TestCase::from_iter(vec!["0", "0"]))
+                .push_case(TestCase::from_iter(vec!["42", r#""Hello World""#]))
+                .push_case(TestCase::from_iter(vec!["some()", "other()"]))
+                .take();
+        info.attributes.add_auto_names();
+
+        let tests = TestsGroup::from(parametrize(item_fn, info)).get_all_tests();
+
+        assert_eq!(
+            vec!["case_1_0_0", "case_2_42_hello_world", "case_3"],
+            tests
+                .iter()
+                .map(|t| t.sig.ident.to_string())
+                .collect::<Vec<_>>()
+        );
+    }
+
     #[rstest]
     #[case::sync(false)]
     #[case::async_fn(true)]
@@ -861,7 +1107,7 @@ mod cases_should {
 mod matrix_cases_should {
     use rstest_test::{assert_in, assert_not_in};
 
-    use crate::parse::vlist::ValueList;
+    use crate::parse::{exclude::Exclusion, vlist::ValueList};
 
     /// Should test matrix tests render without take in account MatrixInfo to RsTestInfo
     /// transformation
@@ -931,6 +1177,148 @@ mod matrix_cases_should {
         assert_eq!(0, inner_functions.filter(|f| f.sig.ident == t_name).count());
     }
 
+    #[test]
+    fn define_a_test_group_that_contains_all_tests() {
+        let item_fn: ItemFn = r#"fn test(first: u32, second: u32) {}"#.ast();
+        let info = RsTestInfo {
+            data: RsTestData {
+                items: vec![
+                    values_list("first", &["1", "2"]).into(),
+                    values_list("second", &["1", "2", "3"]).into(),
+                ],
+            },
+            ..Default::default()
+        };
+
+        let tokens = matrix(item_fn, info);
+
+        let output = TestsGroup::from(tokens);
+
+        assert_eq!(
+            output.test_group_definition().unwrap(),
+            quote! { rstest::scope::TestGroup::new(6usize) }.to_string()
+        );
+        assert_eq!(6, output.get_all_tests().len());
+    }
+
+    #[test]
+    fn use_case_values_overrides() {
+        let item_fn: ItemFn = r#"fn test(a: u32, first: u32, second: u32) {}"#.ast();
+        let mut overridden: TestCase = "2".into();
+        overridden.overrides = vec![values_list("first", &["7"])];
+        let info = RsTestInfo {
+            data: RsTestData {
+                items: vec![
+                    ident("a").into(),
+                    TestCase::from("1").into(),
+                    overridden.into(),
+                    values_list("first", &["1", "2"]).into(),
+                    values_list("second", &["1", "2", "3"]).into(),
+                ],
+            },
+            ..Default::default()
+        };
+
+        let tokens = matrix(item_fn, info);
+
+        assert_in!(tokens.to_string(), quote! { let first = 7; }.to_string());
+
+        let output = TestsGroup::from(tokens);
+
+        assert_eq!(
+            output.test_group_definition().unwrap(),
+            quote! { rstest::scope::TestGroup::new(9usize) }.to_string()
+        );
+        assert_eq!(9, output.get_all_tests().len());
+    }
+
+    #[test]
+    fn zip_values_lists() {
+        let item_fn: ItemFn = r#"fn test(first: u32, second: u32) {}"#.ast();
+        let mut info: RsTestInfo = RsTestData {
+            items: vec![
+                values_list("first", &["1", "2", "3"]).into(),
+                values_list("second", &["4", "5", "6"]).into(),
+            ],
+        }
+        .into();
+        info.attributes.add_matrix_modes(vec![ident("zip")]);
+
+        let tokens = matrix(item_fn, info);
+
+        let output = TestsGroup::from(tokens);
+
+        let tests = output.get_all_tests();
+        assert_eq!(3, tests.len());
+        assert_eq!(
+            output.test_group_definition().unwrap(),
+            quote! { rstest::scope::TestGroup::new(3usize) }.to_string()
+        );
+        for (module, test) in output.get_modules().into_iter().zip(tests) {
+            let module = module.ident.to_string();
+            let index = module.trim_start_matches("first_");
+            assert_eq!(format!("second_{}", index), test.sig.ident.to_string());
+        }
+    }
+
+    #[test]
+    fn pairwise_values_lists() {
+        let item_fn: ItemFn = r#"fn test(a: u32, b: u32, c: u32) {}"#.ast();
+        let mut info: RsTestInfo = RsTestData {
+            items: vec![
+                values_list("a", &["1", "2"]).into(),
+                values_list("b", &["1", "2"]).into(),
+                values_list("c", &["1", "2"]).into(),
+            ],
+        }
+        .into();
+        info.attributes.add_matrix_modes(vec![ident("pairwise")]);
+
+        let tokens = matrix(item_fn, info);
+
+        let output = TestsGroup::from(tokens);
+
+        assert_eq!(4, output.get_all_tests().len());
+        assert_eq!(
+            output.test_group_definition().unwrap(),
+            quote! { rstest::scope::TestGroup::new(4usize) }.to_string()
+        );
+    }
+
+    #[test]
+    fn not_render_excluded_tests() {
+        let item_fn: ItemFn = r#"fn test(first: u32, second: u32) {}"#.ast();
+        let info: RsTestInfo = RsTestData {
+            items: vec![
+                values_list("first", &["1", "2"]).into(),
+                values_list("second", &["3", "4"]).into(),
+                "first = 1, second = 4".ast::<Exclusion>().into(),
+                "first = 2".ast::<Exclusion>().into(),
+            ],
+        }
+        .into();
+
+        let tokens = matrix(item_fn, info);
+
+        let output = TestsGroup::from(tokens);
+
+        let modules = output.get_modules();
+        assert_eq!(1, modules.len());
+        assert_eq!("first_1", &modules[0].ident.to_string());
+        assert_eq!(
+            vec!["second_1"],
+            modules[0]
+                .get_tests()
+                .iter()
+                .map(|t| t.sig.ident.to_string())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            output.test_group_definition().unwrap(),
+            quote! { rstest::scope::TestGroup::new(1usize) }.to_string()
+        );
+    }
+
     #[test]
     fn not_copy_should_panic_attribute() {
         let item_fn =
@@ -986,9 +1374,10 @@ mod matrix_cases_should {
 
         let tests = TestsGroup::from(tokens).get_tests();
 
-        assert_eq!(tests[0].sig.output, item_fn.sig.output);
-        assert_eq!(tests[1].sig.output, item_fn.sig.output);
-        assert_eq!(tests[2].sig.output, item_fn.sig.output);
+        let output: syn::ReturnType = "-> rstest::timeout::TestOutput<Result<i32, String> >".ast();
+        assert_eq!(tests[0].sig.output, output);
+        assert_eq!(tests[1].sig.output, output);
+        assert_eq!(tests[2].sig.output, output);
     }
 
     #[test]
@@ -1051,6 +1440,66 @@ mod matrix_cases_should {
         assert!(&tests[0].sig.ident.to_string().starts_with("fix_"))
     }
 
+    #[test]
+    fn use_value_labels_instead_of_index() {
+        let mut vlist = values_list("fix", &["1", "2", "3"]);
+        vlist.values[0].label = Some(ident("first"));
+        vlist.values[2].label = Some(ident("last"));
+        let info = RsTestInfo {
+            data: RsTestData {
+                items: vec![vlist.into()],
+            },
+            ..Default::default()
+        };
+
+        let item_fn = r#"fn test(fix: u32) { println!("user code") }"#.ast();
+
+        let tests = TestsGroup::from(matrix(item_fn, info)).get_tests();
+
+        assert_eq!(
+            vec!["fix_first", "fix_2", "fix_last"],
+            tests
+                .iter()
+                .map(|t| t.sig.ident.to_string())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[rstest]
+    #[case::literals(
+        &[r#""127.0.0.1:8000""#, "42", "vec![1]"],
+        &["addr_127_0_0_1_8000", "addr_42", "addr_3"]
+    )]
+    #[case::not_unique_names(
+        &[r#""a b""#, r#""a-b""#, "c"],
+        &["addr_1", "addr_2", "addr_3"]
+    )]
+    #[case::collide_with_index(&["2", "two()"], &["addr_1", "addr_2"])]
+    fn derive_values_names_from_literals_in_auto_names_mode(
+        #[case] values: &[&str],
+        #[case] expected: &[&str],
+    ) {
+        let mut info = RsTestInfo {
+            data: RsTestData {
+                items: vec![values_list("addr", values).into()],
+            },
+            ..Default::default()
+        };
+        info.attributes.add_auto_names();
+
+        let item_fn = r#"fn test(addr: String) { println!("user code") }"#.ast();
+
+        let tests = TestsGroup::from(matrix(item_fn, info)).get_tests();
+
+        assert_eq!(
+            expected,
+            tests
+                .iter()
+                .map(|t| t.sig.ident.to_string())
+                .collect::<Vec<_>>()
+        );
+    }
+
     #[rstest]
     #[case::sync(false)]
     #[case::async_fn(true)]
@@ -142,7 +142,7 @@ pub(crate) fn arg_value(name: impl AsRef<str>, value: impl AsRef<str>) -> Argume
 pub(crate) fn values_list<S: AsRef<str>>(arg: &str, values: &[S]) -> ValueList {
     ValueList {
         arg: ident(arg),
-        values: values.into_iter().map(|s| expr(s)).collect(),
+        values: values.into_iter().map(|s| expr(s).into()).collect(),
     }
 }
 
@@ -184,7 +184,7 @@ impl ExtractArgs for TestCase {
 
 impl ExtractArgs for ValueList {
     fn args(&self) -> Vec<Expr> {
-        self.values.iter().cloned().collect()
+        self.values.iter().map(|v| v.expr.clone()).collect()
     }
 }
 
@@ -237,6 +237,8 @@ impl<A: AsRef<str>> FromIterator<A> for TestCase {
             args: iter.into_iter().map(expr).collect(),
             attrs: Default::default(),
             description: None,
+            text_description: None,
+            overrides: vec![],
         }
     }
 }
@@ -310,6 +312,26 @@ impl crate::parse::fixture::FixtureInfo {
         self.attributes = self.attributes.with_once();
         self
     }
+
+    pub(crate) fn with_once_drop(mut self) -> Self {
+        self.attributes = self.attributes.with_once_drop();
+        self
+    }
+
+    pub(crate) fn with_once_reset(mut self) -> Self {
+        self.attributes = self.attributes.with_once_reset();
+        self
+    }
+
+    pub(crate) fn with_module_scope(mut self) -> Self {
+        self.attributes = self.attributes.with_module_scope();
+        self
+    }
+
+    pub(crate) fn with_teardown(mut self, teardown: &str) -> Self {
+        self.attributes = self.attributes.with_teardown(teardown);
+        self
+    }
 }
 
 impl crate::parse::fixture::FixtureModifiers {
@@ -317,4 +339,24 @@ impl crate::parse::fixture::FixtureModifiers {
         self.append(Attribute::attr("once"));
         self
     }
+
+    pub(crate) fn with_once_drop(mut self) -> Self {
+        self.append(Attribute::tagged("once", vec!["drop"]));
+        self
+    }
+
+    pub(crate) fn with_once_reset(mut self) -> Self {
+        self.append(Attribute::tagged("once", vec!["reset"]));
+        self
+    }
+
+    pub(crate) fn with_module_scope(mut self) -> Self {
+        self.set_scope(ident("module"));
+        self
+    }
+
+    pub(crate) fn with_teardown(mut self, teardown: &str) -> Self {
+        self.set_teardown(expr(teardown));
+        self
+    }
 }
@@ -24,6 +24,48 @@ pub(crate) fn fn_args(item_fn: &ItemFn) -> impl Iterator<Item = &FnArg> {
     item_fn.sig.inputs.iter()
 }
 
+#[derive(Default)]
+struct SearchImpl(bool);
+
+impl<'ast> syn::visit::Visit<'ast> for SearchImpl {
+    fn visit_type(&mut self, i: &'ast syn::Type) {
+        if self.0 {
+            return;
+        }
+        if let syn::Type::ImplTrait(_) = i {
+            self.0 = true
+        }
+        syn::visit::visit_type(self, i);
+    }
+}
+
+impl SearchImpl {
+    fn function_has_some_impl(f: &ItemFn) -> bool {
+        let mut s = SearchImpl::default();
+        syn::visit::visit_item_fn(&mut s, f);
+        s.0
+    }
+
+    fn type_has_some_impl(t: &Type) -> bool {
+        use syn::visit::Visit;
+        let mut s = SearchImpl::default();
+        s.visit_type(t);
+        s.0
+    }
+}
+
+/// Return if the function has some generic parameters or use `impl` types
+///
+pub(crate) fn has_some_generics(item_fn: &ItemFn) -> bool {
+    !item_fn.sig.generics.params.is_empty() || SearchImpl::function_has_some_impl(item_fn)
+}
+
+/// Return if the type contains some `impl` type
+///
+pub(crate) fn type_has_some_impl(t: &Type) -> bool {
+    SearchImpl::type_has_some_impl(t)
+}
+
 pub(crate) fn attr_ends_with(attr: &Attribute, segment: &syn::PathSegment) -> bool {
     attr.path.segments.iter().last() == Some(segment)
 }
@@ -270,6 +312,48 @@ pub(crate) fn fn_arg_mutability(arg: &FnArg) -> Option<syn::token::Mut> {
     }
 }
 
+/// The max length of a name fragment derived from code
+pub(crate) const MAX_NAME_LEN: usize = 32;
+
+/// Make `s` usable as a part of an identifier: lowercase ascii alphanumeric chars
+/// separated by a single `_` and at most `MAX_NAME_LEN` chars long.
+pub(crate) fn sanitize_name(s: &str) -> String {
+    let mut name = String::new();
+    for c in s.chars() {
+        if c.is_ascii_alphanumeric() {
+            name.push(c.to_ascii_lowercase());
+        } else if !name.is_empty() && !name.ends_with('_') {
+            name.push('_');
+        }
+    }
+    name.truncate(MAX_NAME_LEN);
+    name.trim_end_matches('_').to_owned()
+}
+
+/// Derive a name from a simple literal expression (or a negative number), `None`
+/// for all other expressions.
+pub(crate) fn literal_name(expr: &Expr) -> Option<String> {
+    let name = match expr {
+        Expr::Lit(syn::ExprLit { lit, .. }) => match lit {
+            syn::Lit::Str(s) => s.value(),
+            syn::Lit::ByteStr(s) => String::from_utf8_lossy(&s.value()).into_owned(),
+            syn::Lit::Char(c) => c.value().to_string(),
+            syn::Lit::Byte(b) => (b.value() as char).to_string(),
+            syn::Lit::Int(i) => i.base10_digits().to_owned(),
+            syn::Lit::Float(f) => f.base10_digits().to_owned(),
+            syn::Lit::Bool(b) => b.value.to_string(),
+            _ => return None,
+        },
+        Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) if matches!(expr.as_ref(), Expr::Lit(_)) => format!("minus_{}", literal_name(expr)?),
+        _ => return None,
+    };
+    Some(sanitize_name(&name)).filter(|n| !n.is_empty())
+}
+
 #[cfg(test)]
 mod test {
     use syn::parse_quote;
@@ -289,6 +373,29 @@ mod test {
         assert_eq!("second", args.next().unwrap().to_string());
     }
 
+    #[rstest]
+    #[case::generics("fn f<G: SomeTrait>(){}", true)]
+    #[case::const_generics("fn f<const N: usize>(){}", true)]
+    #[case::lifetimes("fn f<'a>(){}", true)]
+    #[case::use_impl_in_answer("fn f() -> impl Iterator<Item=u32>{}", true)]
+    #[case::use_impl_in_argumets("fn f(it: impl Iterator<Item=u32>){}", true)]
+    #[case::no_generics("fn f(a: u32) -> String {}", false)]
+    fn has_some_generics_should(#[case] f: &str, #[case] expected: bool) {
+        let f: ItemFn = f.ast();
+
+        assert_eq!(expected, has_some_generics(&f));
+    }
+
+    #[rstest]
+    #[case::simple("impl Iterator<Item=u32>", true)]
+    #[case::nested("Vec<impl ToString>", true)]
+    #[case::no_impl("Vec<T>", false)]
+    fn type_has_some_impl_should(#[case] t: &str, #[case] expected: bool) {
+        let t: Type = t.ast();
+
+        assert_eq!(expected, type_has_some_impl(&t));
+    }
+
     #[test]
     fn fn_args_has_ident_should() {
         let item_fn = parse_quote! {
@@ -387,4 +494,35 @@ mod test {
 
         assert_eq!(expected.sig.generics, cleaned);
     }
+
+    #[rstest]
+    #[case::simple("hello", "hello")]
+    #[case::upper_case("Hello World", "hello_world")]
+    #[case::collapse_separators("127.0.0.1:8000", "127_0_0_1_8000")]
+    #[case::trim(" --hello-- ", "hello")]
+    #[case::not_ascii("ñandú", "and")]
+    #[case::truncate(
+        "a_very_long_name_that_should_be_truncated_somewhere",
+        "a_very_long_name_that_should_be"
+    )]
+    fn sanitize_name_should(#[case] s: &str, #[case] expected: &str) {
+        assert_eq!(expected, sanitize_name(s));
+    }
+
+    #[rstest]
+    #[case::string(r#""hello world""#, Some("hello_world"))]
+    #[case::int("42", Some("42"))]
+    #[case::int_with_suffix("42_u8", Some("42"))]
+    #[case::float("1.5", Some("1_5"))]
+    #[case::negative("-3", Some("minus_3"))]
+    #[case::bool("true", Some("true"))]
+    #[case::char("'a'", Some("a"))]
+    #[case::byte_string(r#"b"data""#, Some("data"))]
+    #[case::empty_string(r#""""#, None)]
+    #[case::no_alphanumeric(r#""!?""#, None)]
+    #[case::not_a_literal("some_value", None)]
+    #[case::call("f(42)", None)]
+    fn literal_name_should(#[case] code: &str, #[case] expected: Option<&str>) {
+        assert_eq!(expected.map(ToOwned::to_owned), literal_name(&expr(code)));
+    }
 }
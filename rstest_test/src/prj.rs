@@ -48,6 +48,7 @@ pub struct Project {
     root: PathBuf,
     channel: Channel,
     nocapture: bool,
+    env: Vec<(OsString, OsString)>,
     ws: Arc<std::sync::RwLock<()>>,
 }
 
@@ -60,6 +61,7 @@ impl Project {
             name: "project".into(),
             channel: Default::default(),
             nocapture: false,
+            env: Vec::new(),
             ws: Arc::new(std::sync::RwLock::new(())),
         }
         .create()
@@ -74,6 +76,13 @@ impl Project {
         self
     }
 
+    /// Set an environment variable when run the tests
+    pub fn with_env<K: AsRef<OsStr>, V: AsRef<OsStr>>(mut self, key: K, value: V) -> Self {
+        self.env
+            .push((key.as_ref().to_owned(), value.as_ref().to_owned()));
+        self
+    }
+
     pub fn subproject<O: AsRef<OsStr>>(&self, name: O) -> Self {
         let _guard = self.ws.write().expect("Cannot lock workspace resource");
         self.workspace_add(name.as_ref().to_str().unwrap());
@@ -82,6 +91,7 @@ impl Project {
             name: name.as_ref().to_owned(),
             channel: self.channel.clone(),
             nocapture: self.nocapture,
+            env: self.env.clone(),
             ws: self.ws.clone(),
         }
         .create()
@@ -105,7 +115,8 @@ impl Project {
 
         cmd.current_dir(&self.path())
             .arg(&self.cargo_channel_arg())
-            .arg("test");
+            .arg("test")
+            .envs(self.env.iter().map(|(k, v)| (k, v)));
 
         if self.nocapture {
             cmd.args(["--", "--nocapture"]);